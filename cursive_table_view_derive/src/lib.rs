@@ -0,0 +1,369 @@
+//! Derive macro for the `TableViewItem` trait of
+//! [`cursive_table_view`](https://docs.rs/cursive_table_view).
+//!
+//! This crate is re-exported by `cursive_table_view` when its `derive`
+//! feature is enabled and should not be used directly.
+#![deny(missing_docs)]
+
+// Crate Dependencies ---------------------------------------------------------
+extern crate proc_macro;
+extern crate proc_macro2;
+#[macro_use]
+extern crate quote;
+extern crate syn;
+
+// External Dependencies ------------------------------------------------------
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use syn::{
+    parse_macro_input, Data, DeriveInput, Error, Fields, Ident, Lit, Meta, NestedMeta, Path, Result,
+};
+
+/// Derives `TableViewItem` for a struct with named fields.
+///
+/// A column enum named `<Struct>Column` is generated with one variant per
+/// field, along with a `<Struct>::table_view()` function which returns a
+/// `TableView` containing all columns.
+///
+/// Columns are displayed via `Display` and sorted via `Ord` unless
+/// configured otherwise.
+///
+/// # Struct Attributes
+///
+/// * `#[table(column = "Name")]` sets the name of the generated column enum.
+///
+/// # Field Attributes
+///
+/// * `#[table(title = "Title")]` sets the column title, which defaults to the
+///   field name.
+/// * `#[table(width = 10)]` sets the absolute width of the column.
+/// * `#[table(width_percent = 20)]` sets the relative width of the column.
+/// * `#[table(align = "right")]` aligns the column `left`, `center` or
+///   `right`.
+/// * `#[table(format = "path::to::fn")]` formats the field via a
+///   `fn(&Field) -> String`.
+/// * `#[table(cmp = "path::to::fn")]` compares the field via a
+///   `fn(&Field, &Field) -> Ordering`, e.g. for floats.
+/// * `#[table(skip)]` excludes the field from the table.
+#[proc_macro_derive(TableViewItem, attributes(table))]
+pub fn derive_table_view_item(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(&input)
+        .unwrap_or_else(|err| err.to_compile_error())
+        .into()
+}
+
+#[derive(Default)]
+struct FieldOptions {
+    title: Option<String>,
+    width: Option<TokenStream2>,
+    align: Option<TokenStream2>,
+    format: Option<Path>,
+    cmp: Option<Path>,
+    skip: bool,
+}
+
+struct Column {
+    field: Ident,
+    variant: Ident,
+    options: FieldOptions,
+}
+
+fn expand(input: &DeriveInput) -> Result<TokenStream2> {
+    let name = &input.ident;
+    let vis = &input.vis;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let fields = match input.data {
+        Data::Struct(ref data) => match data.fields {
+            Fields::Named(ref fields) => &fields.named,
+            _ => {
+                return Err(Error::new_spanned(
+                    input,
+                    "TableViewItem can only be derived for structs with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(Error::new_spanned(
+                input,
+                "TableViewItem can only be derived for structs",
+            ))
+        }
+    };
+
+    let mut enum_name = Ident::new(&format!("{}Column", name), Span::call_site());
+    for meta in table_attributes(&input.attrs)? {
+        match meta {
+            Meta::NameValue(ref nv) if nv.path.is_ident("column") => {
+                enum_name = Ident::new(&lit_str(&nv.lit)?, Span::call_site());
+            }
+            meta => return Err(Error::new_spanned(meta, "unknown table attribute")),
+        }
+    }
+
+    let mut columns = Vec::new();
+    for field in fields {
+        let ident = field.ident.clone().unwrap();
+        let options = field_options(&field.attrs)?;
+        if !options.skip {
+            columns.push(Column {
+                variant: Ident::new(&camel_case(&ident.to_string()), ident.span()),
+                field: ident,
+                options,
+            });
+        }
+    }
+
+    let variants = columns.iter().map(|c| &c.variant).collect::<Vec<_>>();
+    let titles = columns
+        .iter()
+        .map(|c| {
+            c.options
+                .title
+                .clone()
+                .unwrap_or_else(|| title_case(&c.field.to_string()))
+        })
+        .collect::<Vec<_>>();
+
+    let to_column = columns.iter().map(|c| {
+        let (variant, field) = (&c.variant, &c.field);
+        match c.options.format {
+            Some(ref format) => quote! { #enum_name::#variant => #format(&self.#field) },
+            None => quote! { #enum_name::#variant => self.#field.to_string() },
+        }
+    });
+
+    let cmp = columns.iter().map(|c| {
+        let (variant, field) = (&c.variant, &c.field);
+        match c.options.cmp {
+            Some(ref cmp) => quote! { #enum_name::#variant => #cmp(&self.#field, &other.#field) },
+            None => quote! {
+                #enum_name::#variant => ::std::cmp::Ord::cmp(&self.#field, &other.#field)
+            },
+        }
+    });
+
+    let add_columns = columns.iter().zip(&titles).map(|(c, title)| {
+        let variant = &c.variant;
+        let width = c.options.width.iter();
+        let align = c.options.align.iter();
+        quote! {
+            .column(#enum_name::#variant, #title, |c| c #(.#width)* #(.#align)*)
+        }
+    });
+
+    let doc = format!("Columns of [`{}`](struct.{}.html).", name, name);
+    Ok(quote! {
+        #[doc = #doc]
+        #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+        #vis enum #enum_name {
+            #(
+                #[allow(missing_docs)]
+                #variants,
+            )*
+        }
+
+        #[allow(dead_code)]
+        impl #enum_name {
+            /// Returns the title of the column.
+            #vis fn title(&self) -> &'static str {
+                match *self {
+                    #(#enum_name::#variants => #titles,)*
+                }
+            }
+        }
+
+        impl #impl_generics ::cursive_table_view::TableViewItem<#enum_name>
+            for #name #ty_generics #where_clause
+        {
+            fn to_column(&self, column: #enum_name) -> String {
+                match column {
+                    #(#to_column,)*
+                }
+            }
+
+            fn cmp(&self, other: &Self, column: #enum_name) -> ::std::cmp::Ordering
+            where
+                Self: Sized,
+            {
+                match column {
+                    #(#cmp,)*
+                }
+            }
+        }
+
+        #[allow(dead_code)]
+        impl #impl_generics #name #ty_generics #where_clause {
+            /// Returns a new table view with a column for each field.
+            #vis fn table_view() -> ::cursive_table_view::TableView<Self, #enum_name> {
+                ::cursive_table_view::TableView::new()
+                    #(#add_columns)*
+            }
+        }
+    })
+}
+
+fn table_attributes(attrs: &[syn::Attribute]) -> Result<Vec<Meta>> {
+    let mut metas = Vec::new();
+    for attr in attrs.iter().filter(|attr| attr.path.is_ident("table")) {
+        match attr.parse_meta()? {
+            Meta::List(list) => {
+                for nested in list.nested {
+                    match nested {
+                        NestedMeta::Meta(meta) => metas.push(meta),
+                        NestedMeta::Lit(lit) => {
+                            return Err(Error::new_spanned(lit, "expected a table attribute"))
+                        }
+                    }
+                }
+            }
+            meta => return Err(Error::new_spanned(meta, "expected #[table(...)]")),
+        }
+    }
+    Ok(metas)
+}
+
+fn field_options(attrs: &[syn::Attribute]) -> Result<FieldOptions> {
+    let mut options = FieldOptions::default();
+    for meta in table_attributes(attrs)? {
+        match meta {
+            Meta::Path(ref path) if path.is_ident("skip") => options.skip = true,
+            Meta::NameValue(ref nv) if nv.path.is_ident("title") => {
+                options.title = Some(lit_str(&nv.lit)?);
+            }
+            Meta::NameValue(ref nv) if nv.path.is_ident("width") => {
+                let width = lit_int(&nv.lit)?;
+                options.width = Some(quote! { width(#width) });
+            }
+            Meta::NameValue(ref nv) if nv.path.is_ident("width_percent") => {
+                let width = lit_int(&nv.lit)?;
+                options.width = Some(quote! { width_percent(#width) });
+            }
+            Meta::NameValue(ref nv) if nv.path.is_ident("align") => {
+                let align = match lit_str(&nv.lit)?.as_str() {
+                    "left" => quote! { Left },
+                    "center" => quote! { Center },
+                    "right" => quote! { Right },
+                    _ => {
+                        return Err(Error::new_spanned(
+                            &nv.lit,
+                            "expected \"left\", \"center\" or \"right\"",
+                        ))
+                    }
+                };
+                options.align = Some(quote! {
+                    align(::cursive_table_view::derive::HAlign::#align)
+                });
+            }
+            Meta::NameValue(ref nv) if nv.path.is_ident("format") => {
+                options.format = Some(lit_path(&nv.lit)?);
+            }
+            Meta::NameValue(ref nv) if nv.path.is_ident("cmp") => {
+                options.cmp = Some(lit_path(&nv.lit)?);
+            }
+            meta => return Err(Error::new_spanned(meta, "unknown table attribute")),
+        }
+    }
+    Ok(options)
+}
+
+fn lit_str(lit: &Lit) -> Result<String> {
+    match *lit {
+        Lit::Str(ref s) => Ok(s.value()),
+        _ => Err(Error::new_spanned(lit, "expected a string literal")),
+    }
+}
+
+fn lit_path(lit: &Lit) -> Result<Path> {
+    match *lit {
+        Lit::Str(ref s) => s.parse(),
+        _ => Err(Error::new_spanned(lit, "expected a string literal")),
+    }
+}
+
+fn lit_int(lit: &Lit) -> Result<usize> {
+    match *lit {
+        Lit::Int(ref i) => i.base10_parse(),
+        _ => Err(Error::new_spanned(lit, "expected an integer literal")),
+    }
+}
+
+fn camel_case(name: &str) -> String {
+    name.split('_')
+        .filter(|part| !part.is_empty())
+        .map(capitalize)
+        .collect()
+}
+
+fn title_case(name: &str) -> String {
+    name.split('_')
+        .filter(|part| !part.is_empty())
+        .map(capitalize)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    // Modules ----------------------------------------------------------------
+    use super::expand;
+
+    fn expand_str(input: &str) -> String {
+        expand(&syn::parse_str(input).unwrap()).unwrap().to_string()
+    }
+
+    fn expand_err(input: &str) -> String {
+        expand(&syn::parse_str(input).unwrap())
+            .unwrap_err()
+            .to_string()
+    }
+
+    #[test]
+    fn should_configure_column_width_and_alignment() {
+        let expanded = expand_str(
+            "struct Foo {
+                #[table(width = 10, align = \"right\")]
+                a: u32,
+                #[table(width_percent = 20, align = \"center\")]
+                b: u32,
+                c: u32,
+            }",
+        );
+        assert!(expanded.contains(
+            "FooColumn :: A , \"A\" , | c | c . width (10usize) \
+             . align (:: cursive_table_view :: derive :: HAlign :: Right)"
+        ));
+        assert!(expanded.contains(
+            "FooColumn :: B , \"B\" , | c | c . width_percent (20usize) \
+             . align (:: cursive_table_view :: derive :: HAlign :: Center)"
+        ));
+        assert!(expanded.contains("FooColumn :: C , \"C\" , | c | c)"));
+    }
+
+    #[test]
+    fn should_keep_generics_on_impls() {
+        let expanded = expand_str("struct Foo<'a, T: Ord> where T: Clone { a: &'a T }");
+        assert!(expanded.contains(
+            "impl < 'a , T : Ord > :: cursive_table_view :: TableViewItem < FooColumn > \
+             for Foo < 'a , T > where T : Clone"
+        ));
+        assert!(expanded.contains("impl < 'a , T : Ord > Foo < 'a , T > where T : Clone"));
+    }
+
+    #[test]
+    fn should_reject_field_attributes_on_structs() {
+        assert_eq!(
+            expand_err("#[table(title = \"Foo\")] struct Foo { a: u32 }"),
+            "unknown table attribute"
+        );
+    }
+}
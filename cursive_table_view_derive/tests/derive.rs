@@ -0,0 +1,87 @@
+// Crate Dependencies ---------------------------------------------------------
+// ----------------------------------------------------------------------------
+extern crate cursive_table_view;
+
+// STD Dependencies -----------------------------------------------------------
+// ----------------------------------------------------------------------------
+use std::cmp::Ordering;
+use std::fmt::Display;
+
+// Modules --------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+use cursive_table_view::{MaybeSync, TableViewItem};
+
+#[derive(TableViewItem)]
+#[table(column = "FileColumn")]
+struct File {
+    #[table(title = "File Name", width = 20)]
+    name: String,
+    #[table(align = "right", width_percent = 10)]
+    #[table(format = "format_size", cmp = "compare_size")]
+    size_in_bytes: u64,
+    #[table(skip)]
+    #[allow(dead_code)]
+    inode: u64,
+}
+
+fn format_size(size: &u64) -> String {
+    format!("{} B", size)
+}
+
+// Reversed, to tell it apart from the default `Ord` comparison
+fn compare_size(a: &u64, b: &u64) -> Ordering {
+    b.cmp(a)
+}
+
+#[derive(TableViewItem)]
+struct Pair<K, V: Display + Ord + MaybeSync>
+where
+    K: Display + Ord + MaybeSync,
+{
+    key: K,
+    value: V,
+}
+
+fn file(name: &str, size_in_bytes: u64) -> File {
+    File {
+        name: name.to_string(),
+        size_in_bytes,
+        inode: 0,
+    }
+}
+
+#[test]
+fn should_title_columns() {
+    assert_eq!(FileColumn::Name.title(), "File Name");
+    assert_eq!(FileColumn::SizeInBytes.title(), "Size In Bytes");
+}
+
+#[test]
+fn should_skip_fields() {
+    // Exhaustive, so a column for `inode` fails to compile
+    let title = |column: FileColumn| match column {
+        FileColumn::Name | FileColumn::SizeInBytes => column.title(),
+    };
+    assert_eq!(title(FileColumn::Name), "File Name");
+}
+
+#[test]
+fn should_format_and_compare_columns() {
+    let (a, b) = (file("a", 1), file("b", 2));
+    assert_eq!(a.to_column(FileColumn::Name), "a");
+    assert_eq!(a.to_column(FileColumn::SizeInBytes), "1 B");
+    assert_eq!(a.cmp(&b, FileColumn::Name), Ordering::Less);
+    assert_eq!(a.cmp(&b, FileColumn::SizeInBytes), Ordering::Greater);
+}
+
+#[test]
+fn should_derive_for_generic_structs() {
+    let (a, b) = (Pair { key: 2, value: "a" }, Pair { key: 1, value: "b" });
+    assert_eq!(a.to_column(PairColumn::Key), "2");
+    assert_eq!(a.cmp(&b, PairColumn::Key), Ordering::Greater);
+    assert_eq!(a.cmp(&b, PairColumn::Value), Ordering::Less);
+
+    let mut table = Pair::<u32, &'static str>::table_view();
+    table.set_items(vec![a, b]);
+    assert_eq!(table.len(), 2);
+}
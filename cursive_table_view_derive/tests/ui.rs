@@ -0,0 +1,8 @@
+// Crate Dependencies ---------------------------------------------------------
+// ----------------------------------------------------------------------------
+extern crate trybuild;
+
+#[test]
+fn should_reject_invalid_attributes() {
+    trybuild::TestCases::new().compile_fail("tests/ui/*.rs");
+}
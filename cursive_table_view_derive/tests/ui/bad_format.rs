@@ -0,0 +1,11 @@
+extern crate cursive_table_view;
+
+use cursive_table_view::TableViewItem;
+
+#[derive(TableViewItem)]
+struct Foo {
+    #[table(format = "not a path")]
+    name: String,
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+extern crate cursive_table_view;
+
+use cursive_table_view::TableViewItem;
+
+#[derive(TableViewItem)]
+struct Foo {
+    #[table(width = "10")]
+    name: String,
+}
+
+fn main() {}
@@ -18,7 +18,9 @@ use rand::Rng;
 
 // Modules --------------------------------------------------------------------
 // ----------------------------------------------------------------------------
-use cursive_table_view::{TableView, TableViewItem};
+use cursive_table_view::{CellValue, ItemIndex, RowIndex, TableAggregate, TableView,
+    TypedTableViewItem,
+};
 
 #[derive(Copy, Clone, PartialEq, Eq, Hash)]
 enum BasicColumn {
@@ -44,23 +46,12 @@ struct Foo {
     rate: usize,
 }
 
-impl TableViewItem<BasicColumn> for Foo {
-    fn to_column(&self, column: BasicColumn) -> String {
+impl TypedTableViewItem<BasicColumn> for Foo {
+    fn value(&self, column: BasicColumn) -> CellValue {
         match column {
-            BasicColumn::Name => self.name.to_string(),
-            BasicColumn::Count => format!("{}", self.count),
-            BasicColumn::Rate => format!("{}", self.rate),
-        }
-    }
-
-    fn cmp(&self, other: &Self, column: BasicColumn) -> Ordering
-    where
-        Self: Sized,
-    {
-        match column {
-            BasicColumn::Name => self.name.cmp(&other.name),
-            BasicColumn::Count => self.count.cmp(&other.count),
-            BasicColumn::Rate => self.rate.cmp(&other.rate),
+            BasicColumn::Name => CellValue::from(self.name.as_str()),
+            BasicColumn::Count => CellValue::Int(self.count as i64),
+            BasicColumn::Rate => CellValue::Int(self.rate as i64),
         }
     }
 }
@@ -71,11 +62,14 @@ fn main() {
     let mut siv = cursive::default();
     let mut table = TableView::<Foo, BasicColumn>::new()
         .column(BasicColumn::Name, "Name", |c| c.width_percent(20))
-        .column(BasicColumn::Count, "Count", |c| c.align(HAlign::Center))
+        .column(BasicColumn::Count, "Count", |c| {
+            c.align(HAlign::Center).aggregate(TableAggregate::Sum)
+        })
         .column(BasicColumn::Rate, "Rate", |c| {
             c.ordering(Ordering::Greater)
                 .align(HAlign::Right)
                 .width_percent(20)
+                .aggregate(TableAggregate::Average)
         });
 
     let mut items = Vec::new();
@@ -99,7 +93,7 @@ fn main() {
         );
     });
 
-    table.set_on_submit(|siv: &mut Cursive, row: usize, index: usize| {
+    table.set_on_submit_item(|siv: &mut Cursive, row: RowIndex, index: ItemIndex| {
         let value = siv
             .call_on_name("table", move |table: &mut TableView<Foo, BasicColumn>| {
                 format!("{:?}", table.borrow_item(index).unwrap())
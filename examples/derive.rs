@@ -0,0 +1,85 @@
+// Crate Dependencies ---------------------------------------------------------
+// ----------------------------------------------------------------------------
+extern crate cursive;
+extern crate cursive_table_view;
+extern crate rand;
+
+// STD Dependencies -----------------------------------------------------------
+// ----------------------------------------------------------------------------
+use std::cmp::Ordering;
+
+// External Dependencies ------------------------------------------------------
+// ----------------------------------------------------------------------------
+use cursive::traits::*;
+use cursive::views::{Dialog, TextView};
+use rand::Rng;
+
+// Modules --------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+use cursive_table_view::{TableView, TableViewItem};
+
+#[derive(Clone, Debug, TableViewItem)]
+struct Foo {
+    #[table(width_percent = 20)]
+    name: String,
+    #[table(align = "center")]
+    count: usize,
+    #[table(title = "Rate (%)", align = "right", width_percent = 20)]
+    #[table(format = "format_rate", cmp = "compare_rate")]
+    rate: f32,
+    #[table(skip)]
+    #[allow(dead_code)]
+    id: usize,
+}
+
+fn format_rate(rate: &f32) -> String {
+    format!("{:.1}", rate)
+}
+
+fn compare_rate(a: &f32, b: &f32) -> Ordering {
+    a.total_cmp(b)
+}
+
+fn main() {
+    let mut rng = rand::thread_rng();
+
+    let mut siv = cursive::default();
+    let mut table = Foo::table_view();
+
+    let mut items = Vec::new();
+    for i in 0..50 {
+        items.push(Foo {
+            name: format!("Name {}", i),
+            count: rng.gen_range(0, 255),
+            rate: rng.gen_range(0.0, 100.0),
+            id: i,
+        });
+    }
+
+    table.set_items(items);
+
+    table.set_on_submit_item(|siv, _row, index| {
+        let value = siv
+            .call_on_name("table", move |table: &mut TableView<Foo, FooColumn>| {
+                let item = table.borrow_item(index).unwrap();
+                format!(
+                    "{}: {}",
+                    FooColumn::Rate.title(),
+                    item.to_column(FooColumn::Rate)
+                )
+            })
+            .unwrap();
+
+        siv.add_layer(
+            Dialog::around(TextView::new(value))
+                .title("Details")
+                .button("Close", |s| {
+                    s.pop_layer();
+                }),
+        );
+    });
+
+    siv.add_layer(Dialog::around(table.with_name("table").min_size((50, 20))).title("Table View"));
+
+    siv.run();
+}
@@ -18,7 +18,7 @@ use rand::Rng;
 
 // Modules --------------------------------------------------------------------
 // ----------------------------------------------------------------------------
-use cursive_table_view::{TableView, TableViewItem};
+use cursive_table_view::{TableLink, TableView, TableViewItem};
 
 #[derive(Copy, Clone, PartialEq, Eq, Hash)]
 enum BasicColumn {
@@ -59,12 +59,18 @@ fn main() {
     let mut siv = cursive::default();
 
     let mut layout = LinearLayout::new(Orientation::Horizontal);
-    layout.add_child(create_table().min_size((32, 20)));
+    layout.add_child(create_table().with_name("left").min_size((32, 20)));
     layout.add_child(ResizedView::with_fixed_size((4, 0), DummyView));
-    layout.add_child(create_table().min_size((32, 20)));
+    layout.add_child(create_table().with_name("right").min_size((32, 20)));
 
     siv.add_layer(Dialog::around(layout).title("Table View Demo"));
 
+    TableLink::<Foo, BasicColumn>::new()
+        .table("left")
+        .table("right")
+        .follow_rows()
+        .install(&mut siv);
+
     siv.run();
 }
 
@@ -0,0 +1,234 @@
+// STD Dependencies -----------------------------------------------------------
+use std::cmp::Ordering;
+use std::fmt;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// A typed value of a single table cell.
+///
+/// Items which return their values as `CellValue`s via
+/// [`TableViewItem::cell`](trait.TableViewItem.html#method.cell) get
+/// formatting, sorting and alignment of their columns for free.
+///
+/// Values are ordered numerically across `Int`, `Float` and `Bytes`, with
+/// `Empty` values sorted after all others.
+#[derive(Clone, Debug)]
+pub enum CellValue {
+    /// A text value.
+    Text(String),
+    /// A signed integer.
+    Int(i64),
+    /// A floating point number.
+    Float(f64),
+    /// A boolean, displayed as `yes` / `no`.
+    Bool(bool),
+    /// A duration, displayed in hours, minutes and seconds.
+    Duration(Duration),
+    /// A size in bytes, displayed with a binary unit prefix.
+    Bytes(u64),
+    /// A point in time, displayed in UTC.
+    Timestamp(SystemTime),
+    /// No value.
+    Empty,
+}
+
+impl CellValue {
+    /// Returns `true` for values which are displayed right aligned by
+    /// default.
+    pub fn is_numeric(&self) -> bool {
+        matches!(
+            *self,
+            CellValue::Int(_) | CellValue::Float(_) | CellValue::Duration(_) | CellValue::Bytes(_)
+        )
+    }
+
+    /// Returns a numeric representation of the value.
+    ///
+    /// Durations are converted to seconds and timestamps to seconds since the
+    /// unix epoch, text is parsed as a number.
+    pub fn as_number(&self) -> Option<f64> {
+        match *self {
+            CellValue::Text(ref text) => text.trim().parse().ok(),
+            CellValue::Int(value) => Some(value as f64),
+            CellValue::Float(value) => Some(value),
+            CellValue::Bool(value) => Some(if value { 1.0 } else { 0.0 }),
+            CellValue::Duration(value) => Some(value.as_secs_f64()),
+            CellValue::Bytes(value) => Some(value as f64),
+            CellValue::Timestamp(value) => Some(match value.duration_since(UNIX_EPOCH) {
+                Ok(since) => since.as_secs_f64(),
+                Err(before) => -before.duration().as_secs_f64(),
+            }),
+            CellValue::Empty => None,
+        }
+    }
+
+    fn rank(&self) -> usize {
+        match *self {
+            CellValue::Int(_) | CellValue::Float(_) | CellValue::Bytes(_) => 0,
+            CellValue::Duration(_) => 1,
+            CellValue::Timestamp(_) => 2,
+            CellValue::Bool(_) => 3,
+            CellValue::Text(_) => 4,
+            CellValue::Empty => 5,
+        }
+    }
+}
+
+impl PartialEq for CellValue {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for CellValue {}
+
+impl PartialOrd for CellValue {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for CellValue {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (CellValue::Int(a), CellValue::Int(b)) => a.cmp(b),
+            (CellValue::Bytes(a), CellValue::Bytes(b)) => a.cmp(b),
+            (CellValue::Text(a), CellValue::Text(b)) => a.cmp(b),
+            (CellValue::Bool(a), CellValue::Bool(b)) => a.cmp(b),
+            (CellValue::Duration(a), CellValue::Duration(b)) => a.cmp(b),
+            (CellValue::Timestamp(a), CellValue::Timestamp(b)) => a.cmp(b),
+            (a, b) if a.rank() == 0 && b.rank() == 0 => {
+                let (a, b) = (a.as_number().unwrap(), b.as_number().unwrap());
+                a.total_cmp(&b)
+            }
+            (a, b) => a.rank().cmp(&b.rank()),
+        }
+    }
+}
+
+impl fmt::Display for CellValue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            CellValue::Text(ref text) => f.write_str(text),
+            CellValue::Int(value) => write!(f, "{}", value),
+            CellValue::Float(value) => write!(f, "{:.2}", value),
+            CellValue::Bool(value) => f.write_str(if value { "yes" } else { "no" }),
+            CellValue::Duration(value) => format_duration(f, value),
+            CellValue::Bytes(value) => format_bytes(f, value),
+            CellValue::Timestamp(value) => format_timestamp(f, value),
+            CellValue::Empty => Ok(()),
+        }
+    }
+}
+
+impl From<String> for CellValue {
+    fn from(value: String) -> Self {
+        CellValue::Text(value)
+    }
+}
+
+impl<'a> From<&'a str> for CellValue {
+    fn from(value: &'a str) -> Self {
+        CellValue::Text(value.to_string())
+    }
+}
+
+impl From<i64> for CellValue {
+    fn from(value: i64) -> Self {
+        CellValue::Int(value)
+    }
+}
+
+impl From<f64> for CellValue {
+    fn from(value: f64) -> Self {
+        CellValue::Float(value)
+    }
+}
+
+impl From<bool> for CellValue {
+    fn from(value: bool) -> Self {
+        CellValue::Bool(value)
+    }
+}
+
+impl From<Duration> for CellValue {
+    fn from(value: Duration) -> Self {
+        CellValue::Duration(value)
+    }
+}
+
+impl From<SystemTime> for CellValue {
+    fn from(value: SystemTime) -> Self {
+        CellValue::Timestamp(value)
+    }
+}
+
+impl<V: Into<CellValue>> From<Option<V>> for CellValue {
+    fn from(value: Option<V>) -> Self {
+        value.map_or(CellValue::Empty, Into::into)
+    }
+}
+
+fn format_duration(f: &mut fmt::Formatter, value: Duration) -> fmt::Result {
+    let seconds = value.as_secs();
+    if seconds >= 3600 {
+        write!(
+            f,
+            "{}h {:02}m {:02}s",
+            seconds / 3600,
+            seconds % 3600 / 60,
+            seconds % 60
+        )
+    } else if seconds >= 60 {
+        write!(f, "{}m {:02}s", seconds / 60, seconds % 60)
+    } else if seconds > 0 {
+        write!(f, "{:.1}s", value.as_secs_f64())
+    } else {
+        write!(f, "{}ms", value.subsec_millis())
+    }
+}
+
+fn format_bytes(f: &mut fmt::Formatter, value: u64) -> fmt::Result {
+    const UNITS: [&str; 6] = ["KiB", "MiB", "GiB", "TiB", "PiB", "EiB"];
+
+    if value < 1024 {
+        return write!(f, "{} B", value);
+    }
+
+    let mut size = value as f64 / 1024.0;
+    let mut unit = 0;
+    while size >= 1024.0 && unit + 1 < UNITS.len() {
+        size /= 1024.0;
+        unit += 1;
+    }
+    write!(f, "{:.1} {}", size, UNITS[unit])
+}
+
+fn format_timestamp(f: &mut fmt::Formatter, value: SystemTime) -> fmt::Result {
+    let seconds = match value.duration_since(UNIX_EPOCH) {
+        Ok(since) => since.as_secs() as i64,
+        Err(before) => -(before.duration().as_secs() as i64),
+    };
+    let (days, time) = (seconds.div_euclid(86_400), seconds.rem_euclid(86_400));
+
+    // Convert days since the epoch into a civil date (proleptic gregorian)
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    write!(
+        f,
+        "{:04}-{:02}-{:02} {:02}:{:02}:{:02}",
+        year,
+        month,
+        day,
+        time / 3600,
+        time % 3600 / 60,
+        time % 60
+    )
+}
@@ -0,0 +1,250 @@
+// STD Dependencies -----------------------------------------------------------
+use std::cmp::Ordering;
+
+// External Dependencies ------------------------------------------------------
+use cursive::{view::ViewWrapper, With};
+
+// Modules --------------------------------------------------------------------
+use super::{CellValue, ItemIndex, TableColumn, TableView, TypedTableViewItem};
+
+/// A single row of a [`DynamicTable`](struct.DynamicTable.html).
+///
+/// Cells are stored by column id, which stays stable when other columns are
+/// added to or removed from the table.
+#[derive(Clone, Debug, Default)]
+pub struct DynamicRow {
+    cells: Vec<CellValue>,
+}
+
+impl TypedTableViewItem<usize> for DynamicRow {
+    fn value(&self, column: usize) -> CellValue {
+        self.cells.get(column).cloned().unwrap_or(CellValue::Empty)
+    }
+}
+
+/// A table whose columns are only known at runtime, e.g. for displaying CSV
+/// files or SQL result sets.
+///
+/// Columns are identified by their names and rows are provided as
+/// `Vec<CellValue>` in the order of the current columns. Columns sort by
+/// their typed cell values.
+///
+/// The underlying [`TableView`](struct.TableView.html) is available via
+/// `get_inner` and `get_inner_mut`, it uses `usize` column ids which can be
+/// mapped back via [`column_name`](#method.column_name).
+///
+/// # Examples
+///
+/// ```rust
+/// # use cursive_table_view::{CellValue, DynamicTable};
+/// let table = DynamicTable::new()
+///     .column("Name", |c| c)
+///     .column("Size", |c| c.width(10))
+///     .rows(vec![
+///         vec![CellValue::from("a.txt"), CellValue::Bytes(2048)],
+///         vec![CellValue::from("b.txt"), CellValue::Empty],
+///     ]);
+/// # assert_eq!(table.column_names(), vec!["Name", "Size"]);
+/// ```
+pub struct DynamicTable {
+    view: TableView<DynamicRow, usize>,
+    columns: Vec<(String, usize)>,
+    free_ids: Vec<usize>,
+    next_id: usize,
+}
+
+impl Default for DynamicTable {
+    /// Creates a new empty `DynamicTable` without any columns.
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DynamicTable {
+    /// Creates a new empty `DynamicTable` without any columns.
+    pub fn new() -> Self {
+        Self {
+            view: TableView::new(),
+            columns: Vec::new(),
+            free_ids: Vec::new(),
+            next_id: 0,
+        }
+    }
+
+    /// Adds a column with the specified name, which is also used as its
+    /// title.
+    ///
+    /// Existing rows have an empty value for the new column.
+    ///
+    /// Chainable variant.
+    pub fn column<S, C>(mut self, name: S, callback: C) -> Self
+    where
+        S: Into<String>,
+        C: FnOnce(TableColumn<DynamicRow, usize>) -> TableColumn<DynamicRow, usize>,
+    {
+        self.add_column(name, callback);
+        self
+    }
+
+    /// Adds a column with the specified name, which is also used as its
+    /// title.
+    ///
+    /// Existing rows have an empty value for the new column. Adding a column
+    /// with the name of an existing column replaces it.
+    pub fn add_column<S, C>(&mut self, name: S, callback: C)
+    where
+        S: Into<String>,
+        C: FnOnce(TableColumn<DynamicRow, usize>) -> TableColumn<DynamicRow, usize>,
+    {
+        let name = name.into();
+        self.remove_column(&name);
+
+        // Ids of removed columns are reused, so rows only ever grow to the
+        // largest number of columns present at the same time
+        let id = self.free_ids.pop().unwrap_or_else(|| {
+            self.next_id += 1;
+            self.next_id - 1
+        });
+        self.view.add_column(id, name.as_str(), callback);
+        self.columns.push((name, id));
+    }
+
+    /// Removes the column with the specified name along with its values.
+    pub fn remove_column(&mut self, name: &str) {
+        if let Some(i) = self.position(name) {
+            let (_, id) = self.columns.remove(i);
+            self.view.remove_column(i);
+
+            for row in self.view.borrow_items_mut() {
+                if let Some(cell) = row.cells.get_mut(id) {
+                    *cell = CellValue::Empty;
+                }
+            }
+            self.free_ids.push(id);
+        }
+    }
+
+    /// Returns the names of all columns in their display order.
+    pub fn column_names(&self) -> Vec<&str> {
+        self.columns.iter().map(|(name, _)| name.as_str()).collect()
+    }
+
+    /// Returns the name of the column with the specified id of the
+    /// underlying [`TableView`](struct.TableView.html).
+    pub fn column_name(&self, id: usize) -> Option<&str> {
+        self.columns
+            .iter()
+            .find(|(_, i)| *i == id)
+            .map(|(name, _)| name.as_str())
+    }
+
+    /// Sets the contained rows of the table.
+    ///
+    /// The values of each row are given in the order of the current columns,
+    /// missing values are empty.
+    ///
+    /// Chainable variant.
+    pub fn rows(self, rows: Vec<Vec<CellValue>>) -> Self {
+        self.with(|t| t.set_rows(rows))
+    }
+
+    /// Sets the contained rows of the table.
+    ///
+    /// The values of each row are given in the order of the current columns,
+    /// missing values are empty.
+    pub fn set_rows(&mut self, rows: Vec<Vec<CellValue>>) {
+        let rows = rows
+            .into_iter()
+            .map(|row| self.row_from_values(row))
+            .collect();
+        self.view.set_items(rows);
+    }
+
+    /// Inserts a new row into the table.
+    ///
+    /// The values are given in the order of the current columns.
+    pub fn insert_row(&mut self, row: Vec<CellValue>) {
+        let row = self.row_from_values(row);
+        self.view.insert_item(row);
+    }
+
+    /// Removes the row at the specified index within the underlying storage
+    /// vector and returns its values in the order of the current columns.
+    pub fn remove_row(&mut self, index: ItemIndex) -> Option<Vec<CellValue>> {
+        let row = self.view.remove_item(index)?;
+        Some(self.row_values_of(&row))
+    }
+
+    /// Returns the values of the row at the specified index within the
+    /// underlying storage vector, in the order of the current columns.
+    pub fn row_values(&self, index: ItemIndex) -> Option<Vec<CellValue>> {
+        self.view
+            .borrow_item(index)
+            .map(|row| self.row_values_of(row))
+    }
+
+    /// Returns the value of the row at the specified index within the
+    /// underlying storage vector for the named column.
+    ///
+    /// Returns `None` if the row has no value for the column, e.g. because
+    /// it was inserted before the column was added.
+    pub fn value(&self, index: ItemIndex, name: &str) -> Option<&CellValue> {
+        let id = self.columns[self.position(name)?].1;
+        self.view
+            .borrow_item(index)?
+            .cells
+            .get(id)
+            .filter(|value| **value != CellValue::Empty)
+    }
+
+    /// Sorts the table by the named column and the passed `order`.
+    pub fn sort_by(&mut self, name: &str, order: Ordering) {
+        if let Some(i) = self.position(name) {
+            self.view.sort_by(self.columns[i].1, order);
+        }
+    }
+
+    /// Returns the name of the currently active sort column along with its
+    /// ordering.
+    pub fn order(&self) -> Option<(&str, Ordering)> {
+        let (id, order) = self.view.order()?;
+        Some((self.column_name(id)?, order))
+    }
+
+    /// Removes all rows from the table.
+    pub fn clear(&mut self) {
+        self.view.clear();
+    }
+
+    /// Returns the number of rows in the table.
+    pub fn len(&self) -> usize {
+        self.view.len()
+    }
+
+    /// Returns `true` if the table has no rows.
+    pub fn is_empty(&self) -> bool {
+        self.view.is_empty()
+    }
+
+    cursive::inner_getters!(self.view: TableView<DynamicRow, usize>);
+
+    fn position(&self, name: &str) -> Option<usize> {
+        self.columns.iter().position(|(n, _)| n == name)
+    }
+
+    fn row_from_values(&self, values: Vec<CellValue>) -> DynamicRow {
+        let mut cells = vec![CellValue::Empty; self.next_id];
+        for ((_, id), value) in self.columns.iter().zip(values) {
+            cells[*id] = value;
+        }
+        DynamicRow { cells }
+    }
+
+    fn row_values_of(&self, row: &DynamicRow) -> Vec<CellValue> {
+        self.columns.iter().map(|(_, id)| row.value(*id)).collect()
+    }
+}
+
+impl ViewWrapper for DynamicTable {
+    cursive::wrap_impl!(self.view: TableView<DynamicRow, usize>);
+}
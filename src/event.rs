@@ -0,0 +1,101 @@
+// STD Dependencies -----------------------------------------------------------
+use std::cmp::Ordering;
+
+// External Dependencies ------------------------------------------------------
+use cursive::{
+    event::{Event, MouseEvent},
+    vec::Vec2,
+};
+
+// Modules --------------------------------------------------------------------
+use super::{ItemIndex, RowIndex, TableAction};
+
+/// The kind of interaction reported by a [`TableEvent`](struct.TableEvent.html).
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum TableEventKind {
+    /// The focus moved to another row.
+    Select,
+    /// The focused item was submitted, e.g. via `<Enter>` or by clicking it.
+    Submit,
+    /// `<Enter>` was pressed while the table contained no items.
+    SubmitEmpty,
+    /// The table was sorted by a column, with the resulting ordering.
+    Sort(Ordering),
+    /// Another column was selected for sorting.
+    ColumnSelect,
+    /// A column header was clicked.
+    HeaderClick,
+    /// A row of the table was right clicked.
+    RightClick,
+    /// A context menu was requested for the focused item, via right click or
+    /// `<Shift+F10>`.
+    ContextMenu,
+    /// A row was clicked twice with the left mouse button within the double
+    /// click interval.
+    DoubleClick,
+    /// A row was clicked with the middle mouse button.
+    MiddleClick,
+    /// A mouse button was released above a row.
+    Release,
+    /// A mouse button is held down while the mouse moves above a row.
+    Hold,
+    /// The table was scrolled by the user.
+    Scroll,
+    /// A key bound to an action without built-in behavior was pressed, e.g.
+    /// `TableAction::Filter`.
+    Action(TableAction),
+}
+
+/// Determines which left clicks submit an item of a
+/// [`TableView`](struct.TableView.html).
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum SubmitMode {
+    /// Clicking the already focused row submits it.
+    #[default]
+    ClickFocused,
+    /// Double clicking a row submits it.
+    DoubleClick,
+    /// Clicks never submit, only `<Enter>` does.
+    Never,
+}
+
+/// The input which caused a [`TableEvent`](struct.TableEvent.html).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TableTrigger {
+    /// A key press or other non mouse event.
+    Key(Event),
+    /// A mouse event.
+    Mouse(MouseEvent),
+}
+
+impl<'a> From<&'a Event> for TableTrigger {
+    fn from(event: &'a Event) -> Self {
+        match *event {
+            Event::Mouse { event, .. } => TableTrigger::Mouse(event),
+            ref event => TableTrigger::Key(event.clone()),
+        }
+    }
+}
+
+/// An interaction with a [`TableView`](struct.TableView.html), passed to the
+/// callback set via [`set_on_event`](struct.TableView.html#method.set_on_event).
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct TableEvent<H> {
+    /// The kind of interaction.
+    pub kind: TableEventKind,
+    /// The focused row after the interaction.
+    pub row: Option<RowIndex>,
+    /// The item of the focused row, `None` for empty tables and group
+    /// headers.
+    pub item: Option<ItemIndex>,
+    /// The column under the mouse, or the selected column for column
+    /// related events triggered via keyboard.
+    pub column: Option<H>,
+    /// The mouse position within the table, with `y` being the row for row
+    /// related events and `0` for header clicks.
+    pub position: Option<Vec2>,
+    /// The input which caused the interaction.
+    pub trigger: TableTrigger,
+}
@@ -0,0 +1,185 @@
+// STD Dependencies -----------------------------------------------------------
+use std::hash::Hash;
+use std::marker::PhantomData;
+use std::mem;
+use std::sync::{Arc, Mutex};
+
+// External Dependencies ------------------------------------------------------
+use cursive::{CbSink, Cursive};
+
+// Modules --------------------------------------------------------------------
+use super::{ItemIndex, MaybeSync, TableView, TableViewItem};
+
+/// A queued modification of a [`TableView`](struct.TableView.html).
+enum TableOp<T> {
+    Push(T),
+    Update(ItemIndex, Box<dyn FnOnce(&mut T) + Send>),
+    Remove(ItemIndex),
+    Clear,
+    Replace(Vec<T>),
+}
+
+struct TableQueue<T> {
+    ops: Vec<TableOp<T>>,
+    scheduled: bool,
+}
+
+/// A thread safe handle for modifying a named
+/// [`TableView`](struct.TableView.html) from outside the UI thread.
+///
+/// Operations are queued and applied on the UI thread via the `Cursive`
+/// callback sink. Operations queued in quick succession are applied together,
+/// resulting in a single re-sort and relayout of the table.
+///
+/// Indices passed to the handle refer to the underlying storage vector at the
+/// time the operation is applied, after all previously queued operations.
+///
+/// # Example
+///
+/// ```ignore
+/// let handle = TableHandle::<Foo, BasicColumn>::new("table", siv.cb_sink().clone());
+///
+/// std::thread::spawn(move || {
+///     handle.push(Foo { .. });
+/// });
+/// ```
+pub struct TableHandle<T, H> {
+    name: Arc<String>,
+    cb_sink: CbSink,
+    queue: Arc<Mutex<TableQueue<T>>>,
+    phantom: PhantomData<fn() -> H>,
+}
+
+impl<T, H> Clone for TableHandle<T, H> {
+    fn clone(&self) -> Self {
+        Self {
+            name: Arc::clone(&self.name),
+            cb_sink: self.cb_sink.clone(),
+            queue: Arc::clone(&self.queue),
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<T, H> TableHandle<T, H>
+where
+    T: TableViewItem<H> + Send + 'static,
+    H: Eq + Hash + Clone + MaybeSync + 'static,
+{
+    /// Creates a new handle for the table view registered under `name`.
+    pub fn new<S: Into<String>>(name: S, cb_sink: CbSink) -> Self {
+        Self {
+            name: Arc::new(name.into()),
+            cb_sink,
+            queue: Arc::new(Mutex::new(TableQueue {
+                ops: Vec::new(),
+                scheduled: false,
+            })),
+            phantom: PhantomData,
+        }
+    }
+
+    /// Queues the insertion of a new item into the table.
+    pub fn push(&self, item: T) {
+        self.queue(TableOp::Push(item));
+    }
+
+    /// Queues a modification of the item at the specified index within the
+    /// underlying storage vector.
+    pub fn update<F>(&self, index: ItemIndex, update: F)
+    where
+        F: FnOnce(&mut T) + Send + 'static,
+    {
+        self.queue(TableOp::Update(index, Box::new(update)));
+    }
+
+    /// Queues the removal of the item at the specified index within the
+    /// underlying storage vector.
+    pub fn remove(&self, index: ItemIndex) {
+        self.queue(TableOp::Remove(index));
+    }
+
+    /// Queues the removal of all items from the table.
+    pub fn clear(&self) {
+        self.queue(TableOp::Clear);
+    }
+
+    /// Queues the replacement of all items of the table.
+    pub fn replace(&self, items: Vec<T>) {
+        self.queue(TableOp::Replace(items));
+    }
+
+    fn queue(&self, op: TableOp<T>) {
+        let schedule = {
+            let mut queue = self.queue.lock().unwrap();
+            queue.ops.push(op);
+            !mem::replace(&mut queue.scheduled, true)
+        };
+
+        // Sending happens outside of the lock, so the UI thread never has to
+        // wait for a producer which is blocked on the channel
+        if schedule {
+            let name = Arc::clone(&self.name);
+            let pending = Arc::clone(&self.queue);
+            let sent = self.cb_sink.send(Box::new(move |siv: &mut Cursive| {
+                let ops = {
+                    let mut queue = pending.lock().unwrap();
+                    queue.scheduled = false;
+                    mem::take(&mut queue.ops)
+                };
+
+                siv.call_on_name(&name, |table: &mut TableView<T, H>| {
+                    table.apply_ops(ops);
+                });
+            }));
+
+            // The UI is gone, there is nothing left to update
+            if sent.is_err() {
+                let mut queue = self.queue.lock().unwrap();
+                queue.scheduled = false;
+                queue.ops.clear();
+            }
+        }
+    }
+}
+
+impl<T, H> TableView<T, H>
+where
+    T: TableViewItem<H>,
+    H: Eq + Hash + Clone + MaybeSync + 'static,
+{
+    fn apply_ops(&mut self, ops: Vec<TableOp<T>>) {
+        let mut needs_sort = false;
+
+        for op in ops {
+            match op {
+                TableOp::Push(item) => {
+                    self.insert_item(item);
+                }
+                TableOp::Update(index, update) => {
+                    if let Some(item) = self.borrow_item_mut(index) {
+                        update(item);
+                        self.mark_changed(index);
+                        needs_sort = true;
+                    }
+                }
+                TableOp::Remove(index) => {
+                    self.remove_item(index);
+                }
+                TableOp::Clear => {
+                    self.clear();
+                    needs_sort = false;
+                }
+                TableOp::Replace(items) => {
+                    self.set_items(items);
+                    needs_sort = false;
+                }
+            }
+        }
+
+        if needs_sort {
+            self.sort();
+        }
+        self.needs_relayout = true;
+    }
+}
@@ -0,0 +1,42 @@
+// STD Dependencies -----------------------------------------------------------
+use std::fmt;
+
+/// The position of a row as currently displayed by a
+/// [`TableView`](struct.TableView.html).
+///
+/// Row indices change whenever the table is sorted, grouped or items are
+/// inserted and removed.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct RowIndex(pub usize);
+
+/// The index of an item within the underlying storage vector of a
+/// [`TableView`](struct.TableView.html).
+///
+/// Item indices are not affected by sorting, but shift when items before
+/// them are removed.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ItemIndex(pub usize);
+
+impl From<RowIndex> for usize {
+    fn from(row: RowIndex) -> Self {
+        row.0
+    }
+}
+
+impl From<ItemIndex> for usize {
+    fn from(item: ItemIndex) -> Self {
+        item.0
+    }
+}
+
+impl fmt::Display for RowIndex {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl fmt::Display for ItemIndex {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
@@ -0,0 +1,208 @@
+// STD Dependencies -----------------------------------------------------------
+use std::collections::HashMap;
+
+// External Dependencies ------------------------------------------------------
+use cursive::{
+    event::{Event, Key},
+    With,
+};
+
+/// An action of a [`TableView`](struct.TableView.html) which can be bound to
+/// a key via a [`TableKeymap`](struct.TableKeymap.html).
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum TableAction {
+    /// Moves the focus one row up, or leaves column selection.
+    FocusUp,
+    /// Moves the focus one row down, or leaves column selection.
+    FocusDown,
+    /// Moves the focus one page up.
+    PageUp,
+    /// Moves the focus one page down.
+    PageDown,
+    /// Moves the focus half a page up.
+    HalfPageUp,
+    /// Moves the focus half a page down.
+    HalfPageDown,
+    /// Moves the focus to the first row.
+    FocusFirst,
+    /// Moves the focus to the last row.
+    FocusLast,
+    /// Expands the focused group or tree item, otherwise selects the next
+    /// column for sorting.
+    ColumnNext,
+    /// Collapses the focused group or tree item, otherwise selects the
+    /// previous column for sorting.
+    ColumnPrev,
+    /// Sorts the table by the selected column, toggling the ordering if it
+    /// is already sorted by it.
+    Sort,
+    /// Submits the focused item, or sorts by the selected column during
+    /// column selection.
+    Submit,
+    /// Groups the table by the selected column during column selection.
+    GroupBy,
+    /// Requests a context menu for the focused item.
+    ContextMenu,
+    /// Toggles the selection of the focused item.
+    ///
+    /// Toggles the checkbox of the focused item if the table has a checkbox
+    /// column, or else its detail row if the table has a detail view. It is
+    /// always reported as a `TableEventKind::Action` to the `on_event`
+    /// callback.
+    ToggleSelect,
+    /// Expands or collapses the detail row of the focused item.
+    ToggleDetail,
+    /// Activates the embedded view in the next cell of the focused row.
+    CellNext,
+    /// Activates the embedded view in the previous cell of the focused row.
+    CellPrev,
+    /// Requests filtering of the table.
+    ///
+    /// Reported as a `TableEventKind::Action` to the `on_event` callback.
+    Filter,
+}
+
+/// A mapping of events to the [`TableAction`](enum.TableAction.html)s of a
+/// [`TableView`](struct.TableView.html).
+///
+/// # Examples
+///
+/// ```rust
+/// # extern crate cursive;
+/// # extern crate cursive_table_view;
+/// # use cursive::event::Event;
+/// # use cursive_table_view::{TableAction, TableKeymap};
+/// # fn main() {
+/// let keymap = TableKeymap::vim().binding(Event::Char('x'), TableAction::ToggleSelect);
+/// assert_eq!(keymap.action(&Event::Char('j')), Some(TableAction::FocusDown));
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct TableKeymap {
+    bindings: HashMap<Event, TableAction>,
+}
+
+impl Default for TableKeymap {
+    /// Creates a new keymap with the default bindings.
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TableKeymap {
+    /// Creates a new keymap with the default bindings.
+    ///
+    /// | Key | Action |
+    /// |-----|--------|
+    /// | `<Up>` / `<Down>` | `FocusUp` / `FocusDown` |
+    /// | `<PageUp>` / `<PageDown>` | `PageUp` / `PageDown` |
+    /// | `<Home>` / `<End>` | `FocusFirst` / `FocusLast` |
+    /// | `<Left>` / `<Right>` | `ColumnPrev` / `ColumnNext` |
+    /// | `<Tab>` / `<Shift+Tab>` | `CellNext` / `CellPrev` |
+    /// | `<Enter>` | `Submit` |
+    /// | `g` | `GroupBy` |
+    /// | `<Shift+F10>` | `ContextMenu` |
+    /// | `<Space>` | `ToggleSelect` |
+    /// | `d` | `ToggleDetail` |
+    /// | `/` | `Filter` |
+    pub fn new() -> Self {
+        Self::empty()
+            .binding(Event::Key(Key::Up), TableAction::FocusUp)
+            .binding(Event::Key(Key::Down), TableAction::FocusDown)
+            .binding(Event::Key(Key::PageUp), TableAction::PageUp)
+            .binding(Event::Key(Key::PageDown), TableAction::PageDown)
+            .binding(Event::Key(Key::Home), TableAction::FocusFirst)
+            .binding(Event::Key(Key::End), TableAction::FocusLast)
+            .binding(Event::Key(Key::Left), TableAction::ColumnPrev)
+            .binding(Event::Key(Key::Right), TableAction::ColumnNext)
+            .binding(Event::Key(Key::Tab), TableAction::CellNext)
+            .binding(Event::Shift(Key::Tab), TableAction::CellPrev)
+            .binding(Event::Key(Key::Enter), TableAction::Submit)
+            .binding(Event::Char('g'), TableAction::GroupBy)
+            .binding(Event::Shift(Key::F10), TableAction::ContextMenu)
+            .binding(Event::Char(' '), TableAction::ToggleSelect)
+            .binding(Event::Char('d'), TableAction::ToggleDetail)
+            .binding(Event::Char('/'), TableAction::Filter)
+    }
+
+    /// Creates a new keymap without any bindings.
+    pub fn empty() -> Self {
+        Self {
+            bindings: HashMap::new(),
+        }
+    }
+
+    /// Creates a new keymap with vim style bindings in addition to the
+    /// default ones.
+    ///
+    /// `j` / `k` move the focus, `h` / `l` select columns, `g` / `G` jump to
+    /// the first and last row, `<Ctrl+u>` / `<Ctrl+d>` move by half pages,
+    /// `<Ctrl+b>` / `<Ctrl+f>` move by pages and `s` sorts. Grouping is bound
+    /// to `b` instead of `g`.
+    pub fn vim() -> Self {
+        Self::new()
+            .binding(Event::Char('k'), TableAction::FocusUp)
+            .binding(Event::Char('j'), TableAction::FocusDown)
+            .binding(Event::CtrlChar('u'), TableAction::HalfPageUp)
+            .binding(Event::CtrlChar('d'), TableAction::HalfPageDown)
+            .binding(Event::CtrlChar('b'), TableAction::PageUp)
+            .binding(Event::CtrlChar('f'), TableAction::PageDown)
+            .binding(Event::Char('g'), TableAction::FocusFirst)
+            .binding(Event::Char('G'), TableAction::FocusLast)
+            .binding(Event::Char('h'), TableAction::ColumnPrev)
+            .binding(Event::Char('l'), TableAction::ColumnNext)
+            .binding(Event::Char('s'), TableAction::Sort)
+            .binding(Event::Char('b'), TableAction::GroupBy)
+    }
+
+    /// Creates a new keymap with emacs style bindings in addition to the
+    /// default ones.
+    ///
+    /// `<Ctrl+p>` / `<Ctrl+n>` move the focus, `<Ctrl+b>` / `<Ctrl+f>` select
+    /// columns, `<Alt+v>` / `<Ctrl+v>` move by pages, `<Alt+<>` / `<Alt+>>`
+    /// jump to the first and last row and `<Ctrl+s>` filters.
+    pub fn emacs() -> Self {
+        Self::new()
+            .binding(Event::CtrlChar('p'), TableAction::FocusUp)
+            .binding(Event::CtrlChar('n'), TableAction::FocusDown)
+            .binding(Event::AltChar('v'), TableAction::PageUp)
+            .binding(Event::CtrlChar('v'), TableAction::PageDown)
+            .binding(Event::AltChar('<'), TableAction::FocusFirst)
+            .binding(Event::AltChar('>'), TableAction::FocusLast)
+            .binding(Event::CtrlChar('b'), TableAction::ColumnPrev)
+            .binding(Event::CtrlChar('f'), TableAction::ColumnNext)
+            .binding(Event::CtrlChar('s'), TableAction::Filter)
+    }
+
+    /// Binds `event` to `action`, replacing any previous binding of `event`.
+    pub fn set_binding(&mut self, event: Event, action: TableAction) {
+        self.bindings.insert(event, action);
+    }
+
+    /// Binds `event` to `action`, replacing any previous binding of `event`.
+    ///
+    /// Chainable variant.
+    pub fn binding(self, event: Event, action: TableAction) -> Self {
+        self.with(|k| k.set_binding(event, action))
+    }
+
+    /// Removes the binding of `event` and returns its action.
+    pub fn remove_binding(&mut self, event: &Event) -> Option<TableAction> {
+        self.bindings.remove(event)
+    }
+
+    /// Returns the action bound to `event`.
+    pub fn action(&self, event: &Event) -> Option<TableAction> {
+        self.bindings.get(event).copied()
+    }
+
+    /// Returns all events bound to `action`.
+    pub fn events(&self, action: TableAction) -> Vec<&Event> {
+        self.bindings
+            .iter()
+            .filter(|(_, a)| **a == action)
+            .map(|(event, _)| event)
+            .collect()
+    }
+}
@@ -11,38 +11,366 @@
 
 // Crate Dependencies ---------------------------------------------------------
 extern crate cursive_core as cursive;
+#[cfg(feature = "derive")]
+extern crate cursive_table_view_derive;
+#[cfg(feature = "rayon")]
+extern crate rayon;
 
 // STD Dependencies -----------------------------------------------------------
+use std::cell::{Cell, RefCell};
 use std::cmp::{self, Ordering};
-use std::collections::HashMap;
-use std::hash::Hash;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet};
+use std::hash::{Hash, Hasher};
+use std::ops::Range;
 use std::rc::Rc;
+use std::sync::atomic::{AtomicU8, Ordering as AtomicOrdering};
+use std::time::{Duration, Instant};
 
 // External Dependencies ------------------------------------------------------
 use cursive::{
     align::HAlign,
     direction::Direction,
-    event::{Callback, Event, EventResult, Key, MouseButton, MouseEvent},
+    event::{AnyCb, Event, EventResult, MouseButton, MouseEvent},
+    menu::MenuTree,
     theme,
     vec::Vec2,
-    view::{scroll, View},
+    view::{scroll, Position, Selector, View},
+    views::MenuPopup,
     Cursive, Printer, Rect, With,
 };
+#[cfg(feature = "rayon")]
+use rayon::slice::ParallelSliceMut;
+
+// Modules --------------------------------------------------------------------
+mod cell;
+mod dynamic;
+mod event;
+mod handle;
+mod index;
+mod keymap;
+mod link;
+mod master_detail;
+pub use cell::CellValue;
+pub use dynamic::{DynamicRow, DynamicTable};
+pub use event::{SubmitMode, TableEvent, TableEventKind, TableTrigger};
+pub use handle::TableHandle;
+pub use index::{ItemIndex, RowIndex};
+pub use keymap::{TableAction, TableKeymap};
+pub use link::TableLink;
+pub use master_detail::MasterDetailView;
+
+/// Derive macro generating a column enum and a `TableViewItem`
+/// implementation for a struct, see the `cursive_table_view_derive` crate.
+#[cfg(feature = "derive")]
+pub use cursive_table_view_derive::TableViewItem;
+
+/// Items used by code generated via `#[derive(TableViewItem)]`.
+#[cfg(feature = "derive")]
+#[doc(hidden)]
+pub mod derive {
+    pub use cursive::align::HAlign;
+}
+
+/// Number of items above which sorting is performed in parallel when the
+/// `rayon` feature is enabled.
+#[cfg(feature = "rayon")]
+pub const PARALLEL_SORT_THRESHOLD: usize = 10_000;
+
+/// Marks items whose emptiness has not been determined yet during a sort.
+const EMPTY_UNKNOWN: u8 = 2;
+
+/// Marker trait for items and columns which can be shared across threads
+/// during sorting.
+///
+/// With the `rayon` feature enabled this is equivalent to `Sync`, otherwise
+/// it is implemented for all types.
+#[cfg(feature = "rayon")]
+pub trait MaybeSync: Sync {}
+
+#[cfg(feature = "rayon")]
+impl<T: Sync> MaybeSync for T {}
+
+/// Marker trait for items and columns which can be shared across threads
+/// during sorting.
+///
+/// With the `rayon` feature enabled this is equivalent to `Sync`, otherwise
+/// it is implemented for all types.
+#[cfg(not(feature = "rayon"))]
+pub trait MaybeSync {}
+
+#[cfg(not(feature = "rayon"))]
+impl<T> MaybeSync for T {}
 
 /// A trait for displaying and sorting items inside a
 /// [`TableView`](struct.TableView.html).
-pub trait TableViewItem<H>: Clone + Sized
+///
+/// Implementors provide `to_column` along with either `cmp` or `cell`. With
+/// `cell`, sorting and alignment are derived from the typed values. Items
+/// which only provide typed values can implement
+/// [`TypedTableViewItem`](trait.TypedTableViewItem.html) instead.
+///
+/// Column ids of type `H` are cloned whenever they are passed to these
+/// methods, so cheap keys such as plain enums are preferable.
+pub trait TableViewItem<H>: Sized + MaybeSync
 where
-    H: Eq + Hash + Copy + Clone + 'static,
+    H: Eq + Hash + Clone + MaybeSync + 'static,
 {
+    /// Method returning the typed value of the item for the specified column
+    /// from type `H`.
+    ///
+    /// By default the value returned by `to_column` is used as text.
+    fn cell(&self, column: H) -> CellValue {
+        CellValue::Text(self.to_column(column))
+    }
+
     /// Method returning a string representation of the item for the
     /// specified column from type `H`.
     fn to_column(&self, column: H) -> String;
 
     /// Method comparing two items via their specified column from type `H`.
+    ///
+    /// By default the values returned by `cell` are compared.
     fn cmp(&self, other: &Self, column: H) -> Ordering
     where
-        Self: Sized;
+        Self: Sized,
+    {
+        self.cell(column.clone()).cmp(&other.cell(column))
+    }
+
+    /// Method returning a numeric representation of the item for the
+    /// specified column from type `H`, used for column aggregates.
+    ///
+    /// By default the value returned by `cell` is converted to a number.
+    fn to_number(&self, column: H) -> Option<f64> {
+        self.cell(column).as_number()
+    }
+}
+
+/// A trait for items inside a [`TableView`](struct.TableView.html) which
+/// only provide typed values.
+///
+/// All implementors are [`TableViewItem`](trait.TableViewItem.html)s, whose
+/// text, sorting, alignment and aggregates are derived from `value`. The text
+/// uses the `Display` implementation of [`CellValue`](enum.CellValue.html).
+///
+/// # Examples
+///
+/// ```rust
+/// # use cursive_table_view::{CellValue, TableView, TypedTableViewItem};
+/// #[derive(Copy, Clone, PartialEq, Eq, Hash)]
+/// enum FileColumn {
+///     Name,
+///     Size,
+/// }
+///
+/// struct File {
+///     name: String,
+///     size: u64,
+/// }
+///
+/// impl TypedTableViewItem<FileColumn> for File {
+///     fn value(&self, column: FileColumn) -> CellValue {
+///         match column {
+///             FileColumn::Name => CellValue::from(self.name.as_str()),
+///             FileColumn::Size => CellValue::Bytes(self.size),
+///         }
+///     }
+/// }
+///
+/// let table = TableView::<File, FileColumn>::new()
+///     .column(FileColumn::Name, "Name", |c| c)
+///     .column(FileColumn::Size, "Size", |c| c);
+/// ```
+pub trait TypedTableViewItem<H>: Sized + MaybeSync
+where
+    H: Eq + Hash + Clone + MaybeSync + 'static,
+{
+    /// Method returning the typed value of the item for the specified column
+    /// from type `H`.
+    fn value(&self, column: H) -> CellValue;
+}
+
+impl<T, H> TableViewItem<H> for T
+where
+    T: TypedTableViewItem<H>,
+    H: Eq + Hash + Clone + MaybeSync + 'static,
+{
+    fn cell(&self, column: H) -> CellValue {
+        self.value(column)
+    }
+
+    fn to_column(&self, column: H) -> String {
+        self.value(column).to_string()
+    }
+}
+
+/// The position of a row within the viewport after
+/// [`scroll_to_row`](struct.TableView.html#method.scroll_to_row).
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ScrollAlign {
+    /// The row becomes the first visible row.
+    Top,
+    /// The row is centered within the viewport.
+    Center,
+    /// The row becomes the last visible row.
+    Bottom,
+    /// The table scrolls as little as possible to make the row visible.
+    Nearest,
+}
+
+/// A summary of all values of a column, shown in the footer of a
+/// [`TableView`](struct.TableView.html).
+///
+/// Summaries are computed over all items of the table and updated whenever
+/// the items change.
+pub enum TableAggregate<T> {
+    /// The sum of all numeric values.
+    Sum,
+    /// The average of all numeric values.
+    Average,
+    /// The smallest numeric value.
+    Min,
+    /// The largest numeric value.
+    Max,
+    /// The number of items.
+    Count,
+    /// The number of distinct values.
+    CountDistinct,
+    /// A custom summary computed from all items.
+    Custom(AggregateCallback<T>),
+}
+
+/// Callback computing a custom column summary from all items.
+///
+/// This is a private type to help readability.
+type AggregateCallback<T> = Rc<dyn Fn(&[&T]) -> String>;
+
+impl<T> TableAggregate<T> {
+    /// Creates a custom summary computed from all items.
+    pub fn custom<F>(f: F) -> Self
+    where
+        F: Fn(&[&T]) -> String + 'static,
+    {
+        TableAggregate::Custom(Rc::new(f))
+    }
+
+    fn apply<H>(&self, items: &[&T], column: &TableColumn<T, H>) -> String
+    where
+        T: TableViewItem<H>,
+        H: Eq + Hash + Clone + MaybeSync + 'static,
+    {
+        let numbers = || items.iter().filter_map(|item| column.number(item));
+        let number = |value: Option<f64>| value.map(format_number).unwrap_or_default();
+
+        match *self {
+            TableAggregate::Sum => format_number(numbers().sum()),
+            TableAggregate::Average => {
+                let (sum, count) = numbers().fold((0.0, 0), |(sum, count), n| (sum + n, count + 1));
+                number(if count > 0 {
+                    Some(sum / count as f64)
+                } else {
+                    None
+                })
+            }
+            TableAggregate::Min => number(numbers().fold(None, |min: Option<f64>, n| {
+                Some(min.map_or(n, |min| min.min(n)))
+            })),
+            TableAggregate::Max => number(numbers().fold(None, |max: Option<f64>, n| {
+                Some(max.map_or(n, |max| max.max(n)))
+            })),
+            TableAggregate::Count => items.len().to_string(),
+            TableAggregate::CountDistinct => items
+                .iter()
+                .map(|item| column.text(item))
+                .collect::<HashSet<_>>()
+                .len()
+                .to_string(),
+            TableAggregate::Custom(ref f) => f(items),
+        }
+    }
+}
+
+/// A trait for items with a stable identity inside a
+/// [`TableView`](struct.TableView.html).
+///
+/// Keyed items can be updated in place and allow the table to keep its
+/// focus on the same logical item when its contents are replaced.
+pub trait KeyedTableViewItem<H>: TableViewItem<H>
+where
+    H: Eq + Hash + Clone + MaybeSync + 'static,
+{
+    /// The type used to identify an item.
+    type Key: Eq + Hash + Clone;
+
+    /// Method returning the key which uniquely identifies this item.
+    fn key(&self) -> Self::Key;
+}
+
+/// A trait for hierarchical items inside a
+/// [`TableView`](struct.TableView.html) in tree mode.
+///
+/// Items are stored in a flat list, with every item reporting the key of its
+/// parent. Items without a parent, or whose parent cannot be found, are shown
+/// at the top level.
+pub trait TreeTableItem<H>: KeyedTableViewItem<H>
+where
+    H: Eq + Hash + Clone + MaybeSync + 'static,
+{
+    /// Method returning the key of this item's parent.
+    fn parent(&self) -> Option<Self::Key>;
+}
+
+/// Lookup table from the hashed keys of items to their indices within the
+/// underlying storage vector.
+struct KeyIndex<T> {
+    hash: fn(&T) -> u64,
+    buckets: HashMap<u64, Vec<usize>>,
+}
+
+impl<T> KeyIndex<T> {
+    fn new(hash: fn(&T) -> u64, items: &[T]) -> Self {
+        let mut index = Self {
+            hash,
+            buckets: HashMap::new(),
+        };
+        for (i, item) in items.iter().enumerate() {
+            index.insert(item, i);
+        }
+        index
+    }
+
+    fn insert(&mut self, item: &T, i: usize) {
+        self.buckets.entry((self.hash)(item)).or_default().push(i);
+    }
+}
+
+/// Function resolving the parent index of every item.
+///
+/// This is a private type to help readability.
+type TreeParents<T> = fn(&[T]) -> Vec<Option<usize>>;
+
+/// Placeholder within `rows_to_items` for rows which show a group header.
+const GROUP_ROW: usize = usize::MAX;
+
+/// A group of items sharing the same value in the grouping column.
+struct TableGroup {
+    key: String,
+    count: usize,
+}
+
+/// An expanded detail row, shown below the row of its item.
+struct DetailRow {
+    view: Box<dyn View>,
+    height: usize,
+}
+
+/// Position of a visible row within the tree.
+#[derive(Clone, Copy)]
+struct TreeRow {
+    depth: usize,
+    parent: Option<usize>,
+    has_children: bool,
 }
 
 /// Callback used when a column is sorted.
@@ -55,7 +383,35 @@ type OnSortCallback<H> = Rc<dyn Fn(&mut Cursive, H, Ordering)>;
 /// Callback taking as argument the row and the index of an element.
 ///
 /// This is a private type to help readability.
-type IndexCallback = Rc<dyn Fn(&mut Cursive, usize, usize)>;
+type IndexCallback = Rc<dyn Fn(&mut Cursive, RowIndex, ItemIndex)>;
+
+/// Callback taking as argument any interaction with the table.
+///
+/// This is a private type to help readability.
+type EventCallback<H> = Rc<dyn Fn(&mut Cursive, TableEvent<H>)>;
+
+/// Callback taking as argument the row, the index and the column of an
+/// element along with the screen position of its context menu.
+///
+/// This is a private type to help readability.
+type ContextMenuCallback<H> = Rc<dyn Fn(&mut Cursive, RowIndex, ItemIndex, H, Vec2)>;
+
+/// Callback taking as argument the index of an element and whether it was
+/// checked or unchecked.
+///
+/// This is a private type to help readability.
+type CheckCallback = Rc<dyn Fn(&mut Cursive, ItemIndex, bool)>;
+
+/// Callback taking as argument the new scroll offset of the table.
+///
+/// This is a private type to help readability.
+type ScrollCallback = Rc<dyn Fn(&mut Cursive, usize)>;
+
+/// Callback creating a view for an item, either for its detail row or for
+/// one of its cells.
+///
+/// This is a private type to help readability.
+type ItemViewCallback<T> = Rc<dyn Fn(&T) -> Box<dyn View>>;
 
 /// View to select an item among a list, supporting multiple columns for sorting.
 ///
@@ -120,23 +476,64 @@ pub struct TableView<T, H> {
     needs_relayout: bool,
 
     column_select: bool,
-    columns: Vec<TableColumn<H>>,
+    columns: Vec<TableColumn<T, H>>,
     column_indicies: HashMap<H, usize>,
 
     focus: usize,
     items: Vec<T>,
     rows_to_items: Vec<usize>,
 
+    // Built on demand by `find_by_key` and dropped whenever indices shift
+    key_index: RefCell<Option<KeyIndex<T>>>,
+
+    follow: bool,
+    following: bool,
+    max_items: Option<usize>,
+
+    changes: HashMap<usize, Instant>,
+    change_highlight: Option<Duration>,
+    change_style: theme::ColorStyle,
+
+    tree: Option<TreeParents<T>>,
+    tree_rows: Vec<TreeRow>,
+    expanded: HashSet<usize>,
+
+    detail_view: Option<ItemViewCallback<T>>,
+    details: HashMap<usize, DetailRow>,
+
+    cell_views: HashMap<(usize, usize), Box<dyn View>>,
+    active_cell: Option<usize>,
+
+    checked: HashSet<usize>,
+
+    group_by: Option<H>,
+    groups: Vec<TableGroup>,
+    row_groups: Vec<Option<usize>>,
+    collapsed_groups: HashSet<String>,
+
+    keymap: TableKeymap,
+    scrolloff: usize,
+    wrap_around: bool,
+    submit_mode: SubmitMode,
+    double_click_interval: Duration,
+    // Time and row of the last left click, used to detect double clicks
+    last_click: Option<(Instant, usize)>,
+
+    // Absolute screen offset of the last draw, used to position popups
+    last_offset: Cell<Vec2>,
+
     on_sort: Option<OnSortCallback<H>>,
-    // TODO Pass drawing offsets into the handlers so a popup menu
-    // can be created easily?
     on_submit: Option<IndexCallback>,
     on_select: Option<IndexCallback>,
+    on_event: Option<EventCallback<H>>,
+    on_context_menu: Option<ContextMenuCallback<H>>,
+    on_scroll: Option<ScrollCallback>,
+    on_check: Option<CheckCallback>,
 }
 
 cursive::impl_scroller!(TableView < T, H > ::scroll_core);
 
-impl<T: TableViewItem<H>, H: Eq + Hash + Copy + Clone + 'static> Default for TableView<T, H> {
+impl<T: TableViewItem<H>, H: Eq + Hash + Clone + MaybeSync + 'static> Default for TableView<T, H> {
     /// Creates a new empty `TableView` without any columns.
     ///
     /// See [`TableView::new()`].
@@ -145,7 +542,7 @@ impl<T: TableViewItem<H>, H: Eq + Hash + Copy + Clone + 'static> Default for Tab
     }
 }
 
-impl<T: TableViewItem<H>, H: Eq + Hash + Copy + Clone + 'static> TableView<T, H> {
+impl<T: TableViewItem<H>, H: Eq + Hash + Clone + MaybeSync + 'static> TableView<T, H> {
     /// Creates a new empty `TableView` without any columns.
     ///
     /// A TableView should be accompanied by a enum of type `H` representing
@@ -164,9 +561,52 @@ impl<T: TableViewItem<H>, H: Eq + Hash + Copy + Clone + 'static> TableView<T, H>
             items: Vec::new(),
             rows_to_items: Vec::new(),
 
+            key_index: RefCell::new(None),
+
+            follow: false,
+            following: true,
+            max_items: None,
+
+            changes: HashMap::new(),
+            change_highlight: None,
+            change_style: theme::ColorStyle::new(
+                theme::Color::Dark(theme::BaseColor::Black),
+                theme::Color::Light(theme::BaseColor::Yellow),
+            ),
+
+            tree: None,
+            tree_rows: Vec::new(),
+            expanded: HashSet::new(),
+
+            detail_view: None,
+            details: HashMap::new(),
+
+            cell_views: HashMap::new(),
+            active_cell: None,
+
+            checked: HashSet::new(),
+
+            group_by: None,
+            groups: Vec::new(),
+            row_groups: Vec::new(),
+            collapsed_groups: HashSet::new(),
+
+            keymap: TableKeymap::default(),
+            scrolloff: 0,
+            wrap_around: false,
+            submit_mode: SubmitMode::default(),
+            double_click_interval: Duration::from_millis(500),
+            last_click: None,
+
+            last_offset: Cell::new(Vec2::zero()),
+
             on_sort: None,
             on_submit: None,
             on_select: None,
+            on_event: None,
+            on_context_menu: None,
+            on_scroll: None,
+            on_check: None,
         }
     }
 
@@ -175,7 +615,7 @@ impl<T: TableViewItem<H>, H: Eq + Hash + Copy + Clone + 'static> TableView<T, H>
     ///
     /// The provided callback can be used to further configure the
     /// created [`TableColumn`](struct.TableColumn.html).
-    pub fn column<S: Into<String>, C: FnOnce(TableColumn<H>) -> TableColumn<H>>(
+    pub fn column<S: Into<String>, C: FnOnce(TableColumn<T, H>) -> TableColumn<T, H>>(
         mut self,
         column: H,
         title: S,
@@ -190,7 +630,7 @@ impl<T: TableViewItem<H>, H: Eq + Hash + Copy + Clone + 'static> TableView<T, H>
     ///
     /// The provided callback can be used to further configure the
     /// created [`TableColumn`](struct.TableColumn.html).
-    pub fn add_column<S: Into<String>, C: FnOnce(TableColumn<H>) -> TableColumn<H>>(
+    pub fn add_column<S: Into<String>, C: FnOnce(TableColumn<T, H>) -> TableColumn<T, H>>(
         &mut self,
         column: H,
         title: S,
@@ -208,6 +648,8 @@ impl<T: TableViewItem<H>, H: Eq + Hash + Copy + Clone + 'static> TableView<T, H>
 
         let column = self.columns.remove(i);
         self.column_indicies.remove(&column.column);
+        self.cell_views.clear();
+        self.active_cell = None;
         self.needs_relayout = true;
     }
 
@@ -216,7 +658,7 @@ impl<T: TableViewItem<H>, H: Eq + Hash + Copy + Clone + 'static> TableView<T, H>
     ///
     /// The provided callback can be used to further configure the
     /// created [`TableColumn`](struct.TableColumn.html).
-    pub fn insert_column<S: Into<String>, C: FnOnce(TableColumn<H>) -> TableColumn<H>>(
+    pub fn insert_column<S: Into<String>, C: FnOnce(TableColumn<T, H>) -> TableColumn<T, H>>(
         &mut self,
         i: usize,
         column: H,
@@ -228,14 +670,16 @@ impl<T: TableViewItem<H>, H: Eq + Hash + Copy + Clone + 'static> TableView<T, H>
             *self.column_indicies.get_mut(&column.column).unwrap() += 1;
         }
 
-        self.column_indicies.insert(column, i);
+        self.column_indicies.insert(column.clone(), i);
         self.columns
-            .insert(i, callback(TableColumn::new(column, title.into())));
+            .insert(i, callback(TableColumn::new(column.clone(), title.into())));
 
         // Make the first colum the default one
         if self.columns.len() == 1 {
             self.set_default_column(column);
         }
+        self.cell_views.clear();
+        self.active_cell = None;
         self.needs_relayout = true;
     }
 
@@ -262,7 +706,7 @@ impl<T: TableViewItem<H>, H: Eq + Hash + Copy + Clone + 'static> TableView<T, H>
     /// Sorts the table using the specified table `column` and the passed
     /// `order`.
     pub fn sort_by(&mut self, column: H, order: Ordering) {
-        if self.column_indicies.contains_key(&column) {
+        if let Some(&index) = self.column_indicies.get(&column) {
             for c in &mut self.columns {
                 c.selected = c.column == column;
                 if c.selected {
@@ -271,15 +715,17 @@ impl<T: TableViewItem<H>, H: Eq + Hash + Copy + Clone + 'static> TableView<T, H>
                     c.order = Ordering::Equal;
                 }
             }
-        }
 
-        self.sort_items(column, order);
+            self.sort_items(index, order);
+        }
     }
 
     /// Sorts the table using the currently active column and its
     /// ordering.
     pub fn sort(&mut self) {
-        if let Some((column, order)) = self.order() {
+        if self.has_row_structure() {
+            self.refresh_rows(None);
+        } else if let Some((column, order)) = self.sort_column() {
             self.sort_items(column, order);
         }
     }
@@ -290,12 +736,8 @@ impl<T: TableViewItem<H>, H: Eq + Hash + Copy + Clone + 'static> TableView<T, H>
     /// Might return `None` if there are currently no items in the table
     /// and it has not been sorted yet.
     pub fn order(&self) -> Option<(H, Ordering)> {
-        for c in &self.columns {
-            if c.order != Ordering::Equal {
-                return Some((c.column, c.order));
-            }
-        }
-        None
+        self.sort_column()
+            .map(|(index, order)| (self.columns[index].column.clone(), order))
     }
 
     /// Disables this view.
@@ -365,15 +807,15 @@ impl<T: TableViewItem<H>, H: Eq + Hash + Copy + Clone + 'static> TableView<T, H>
     /// # Example
     ///
     /// ```ignore
-    /// table.set_on_submit(|siv: &mut Cursive, row: usize, index: usize| {
+    /// table.set_on_submit_item(|siv: &mut Cursive, row: RowIndex, index: ItemIndex| {
     ///
     /// });
     /// ```
-    pub fn set_on_submit<F>(&mut self, cb: F)
+    pub fn set_on_submit_item<F>(&mut self, cb: F)
     where
-        F: Fn(&mut Cursive, usize, usize) + 'static,
+        F: Fn(&mut Cursive, RowIndex, ItemIndex) + 'static,
     {
-        self.on_submit = Some(Rc::new(move |s, row, index| cb(s, row, index)));
+        self.on_submit = Some(Rc::new(cb));
     }
 
     /// Sets a callback to be used when `<Enter>` is pressed while an item
@@ -383,19 +825,11 @@ impl<T: TableViewItem<H>, H: Eq + Hash + Copy + Clone + 'static> TableView<T, H>
     /// within the underlying storage vector will be given to the callback.
     ///
     /// Chainable variant.
-    ///
-    /// # Example
-    ///
-    /// ```ignore
-    /// table.on_submit(|siv: &mut Cursive, row: usize, index: usize| {
-    ///
-    /// });
-    /// ```
-    pub fn on_submit<F>(self, cb: F) -> Self
+    pub fn on_submit_item<F>(self, cb: F) -> Self
     where
-        F: Fn(&mut Cursive, usize, usize) + 'static,
+        F: Fn(&mut Cursive, RowIndex, ItemIndex) + 'static,
     {
-        self.with(|t| t.set_on_submit(cb))
+        self.with(|t| t.set_on_submit_item(cb))
     }
 
     /// Sets a callback to be used when an item is selected.
@@ -406,15 +840,15 @@ impl<T: TableViewItem<H>, H: Eq + Hash + Copy + Clone + 'static> TableView<T, H>
     /// # Example
     ///
     /// ```ignore
-    /// table.set_on_select(|siv: &mut Cursive, row: usize, index: usize| {
+    /// table.set_on_select_item(|siv: &mut Cursive, row: RowIndex, index: ItemIndex| {
     ///
     /// });
     /// ```
-    pub fn set_on_select<F>(&mut self, cb: F)
+    pub fn set_on_select_item<F>(&mut self, cb: F)
     where
-        F: Fn(&mut Cursive, usize, usize) + 'static,
+        F: Fn(&mut Cursive, RowIndex, ItemIndex) + 'static,
     {
-        self.on_select = Some(Rc::new(move |s, row, index| cb(s, row, index)));
+        self.on_select = Some(Rc::new(cb));
     }
 
     /// Sets a callback to be used when an item is selected.
@@ -423,778 +857,4640 @@ impl<T: TableViewItem<H>, H: Eq + Hash + Copy + Clone + 'static> TableView<T, H>
     /// within the underlying storage vector will be given to the callback.
     ///
     /// Chainable variant.
-    ///
-    /// # Example
-    ///
-    /// ```ignore
-    /// table.on_select(|siv: &mut Cursive, row: usize, index: usize| {
-    ///
-    /// });
-    /// ```
-    pub fn on_select<F>(self, cb: F) -> Self
+    pub fn on_select_item<F>(self, cb: F) -> Self
     where
-        F: Fn(&mut Cursive, usize, usize) + 'static,
+        F: Fn(&mut Cursive, RowIndex, ItemIndex) + 'static,
     {
-        self.with(|t| t.set_on_select(cb))
-    }
-
-    /// Removes all items from this view.
-    pub fn clear(&mut self) {
-        self.items.clear();
-        self.rows_to_items.clear();
-        self.focus = 0;
-        self.needs_relayout = true;
+        self.with(|t| t.set_on_select_item(cb))
     }
 
-    /// Returns the number of items in this table.
-    pub fn len(&self) -> usize {
-        self.items.len()
+    /// Sets the keymap used for navigating the table and triggering its
+    /// actions.
+    ///
+    /// Defaults to [`TableKeymap::new`](struct.TableKeymap.html#method.new),
+    /// presets for vim and emacs style bindings are available as well.
+    pub fn set_keymap(&mut self, keymap: TableKeymap) {
+        self.keymap = keymap;
     }
 
-    /// Returns `true` if this table has no items.
-    pub fn is_empty(&self) -> bool {
-        self.items.is_empty()
+    /// Sets the keymap used for navigating the table and triggering its
+    /// actions.
+    ///
+    /// Chainable variant.
+    pub fn keymap(self, keymap: TableKeymap) -> Self {
+        self.with(|t| t.set_keymap(keymap))
     }
 
-    /// Returns the index of the currently selected table row.
-    pub fn row(&self) -> Option<usize> {
-        if self.items.is_empty() {
-            None
-        } else {
-            Some(self.focus)
-        }
+    /// Returns a mutable reference to the keymap of the table, e.g. for
+    /// adding or removing single bindings.
+    pub fn keymap_mut(&mut self) -> &mut TableKeymap {
+        &mut self.keymap
     }
 
-    /// Selects the row at the specified index.
-    pub fn set_selected_row(&mut self, row_index: usize) {
-        self.focus = row_index;
-        self.scroll_core.scroll_to_y(row_index);
+    /// Sets the number of rows kept visible above and below the focused row
+    /// while scrolling.
+    ///
+    /// The margin is reduced on tables too small to fit it. Defaults to `0`.
+    pub fn set_scrolloff(&mut self, rows: usize) {
+        self.scrolloff = rows;
     }
 
-    /// Selects the row at the specified index.
+    /// Sets the number of rows kept visible above and below the focused row
+    /// while scrolling.
     ///
     /// Chainable variant.
-    pub fn selected_row(self, row_index: usize) -> Self {
-        self.with(|t| t.set_selected_row(row_index))
+    pub fn scrolloff(self, rows: usize) -> Self {
+        self.with(|t| t.set_scrolloff(rows))
     }
 
-    /// Sets the contained items of the table.
+    /// Sets whether moving the focus down from the last row continues at
+    /// the first row and vice versa.
     ///
-    /// The currently active sort order is preserved and will be applied to all
-    /// items.
-    pub fn set_items(&mut self, items: Vec<T>) {
-        self.items = items;
-        self.rows_to_items = Vec::with_capacity(self.items.len());
+    /// Defaults to `false`.
+    pub fn set_wrap_around(&mut self, wrap_around: bool) {
+        self.wrap_around = wrap_around;
+    }
 
-        for i in 0..self.items.len() {
-            self.rows_to_items.push(i);
-        }
+    /// Sets whether moving the focus down from the last row continues at
+    /// the first row and vice versa.
+    ///
+    /// Chainable variant.
+    pub fn wrap_around(self, wrap_around: bool) -> Self {
+        self.with(|t| t.set_wrap_around(wrap_around))
+    }
 
-        if let Some((column, order)) = self.order() {
-            self.sort_by(column, order);
-        }
+    /// Sets which left clicks submit an item.
+    ///
+    /// Defaults to [`SubmitMode::ClickFocused`](enum.SubmitMode.html), use
+    /// `SubmitMode::DoubleClick` to avoid accidental submits by single
+    /// clicks.
+    pub fn set_submit_mode(&mut self, mode: SubmitMode) {
+        self.submit_mode = mode;
+    }
 
-        self.set_selected_row(0);
-        self.needs_relayout = true;
+    /// Sets which left clicks submit an item.
+    ///
+    /// Chainable variant.
+    pub fn submit_mode(self, mode: SubmitMode) -> Self {
+        self.with(|t| t.set_submit_mode(mode))
     }
 
-    /// Sets the contained items of the table.
+    /// Sets the maximum time between two clicks on the same row for them to
+    /// count as a double click.
     ///
-    /// The order of the items will be preserved even when the table is sorted.
+    /// Defaults to 500 milliseconds.
+    pub fn set_double_click_interval(&mut self, interval: Duration) {
+        self.double_click_interval = interval;
+    }
+
+    /// Sets the maximum time between two clicks on the same row for them to
+    /// count as a double click.
     ///
     /// Chainable variant.
-    pub fn items(self, items: Vec<T>) -> Self {
-        self.with(|t| t.set_items(items))
+    pub fn double_click_interval(self, interval: Duration) -> Self {
+        self.with(|t| t.set_double_click_interval(interval))
     }
 
-    /// Returns a immmutable reference to the item at the specified index
-    /// within the underlying storage vector.
-    pub fn borrow_item(&self, index: usize) -> Option<&T> {
-        self.items.get(index)
+    /// Sets a callback creating the view shown below an item when its detail
+    /// row is expanded.
+    ///
+    /// Detail rows span all columns and are expanded by pressing `<Space>`
+    /// unless the table has a checkbox column, by pressing `d` or by clicking
+    /// the `▸` marker in front of an item. Mouse events within a
+    /// detail row, as well as keys not handled by the table, are passed to
+    /// the detail view of the focused item.
+    ///
+    /// Replacing the callback collapses all detail rows.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// table.set_detail_view(|item: &Foo| Box::new(TextView::new(item.description.clone())));
+    /// ```
+    pub fn set_detail_view<F>(&mut self, cb: F)
+    where
+        F: Fn(&T) -> Box<dyn View> + 'static,
+    {
+        self.detail_view = Some(Rc::new(cb));
+        self.details.clear();
+        self.needs_relayout = true;
     }
 
-    /// Returns a mutable reference to the item at the specified index within
-    /// the underlying storage vector.
-    pub fn borrow_item_mut(&mut self, index: usize) -> Option<&mut T> {
-        self.items.get_mut(index)
+    /// Sets a callback creating the view shown below an item when its detail
+    /// row is expanded.
+    ///
+    /// Chainable variant.
+    pub fn detail_view<F>(self, cb: F) -> Self
+    where
+        F: Fn(&T) -> Box<dyn View> + 'static,
+    {
+        self.with(|t| t.set_detail_view(cb))
     }
 
-    /// Returns a immmutable reference to the items contained within the table.
-    pub fn borrow_items(&mut self) -> &Vec<T> {
-        &self.items
+    /// Expands the detail row of the item at the specified index within the
+    /// underlying storage vector.
+    ///
+    /// Does nothing without a detail view callback.
+    pub fn expand_detail(&mut self, item: ItemIndex) {
+        if self.details.contains_key(&item.0) {
+            return;
+        }
+        if let (Some(cb), Some(value)) = (self.detail_view.clone(), self.items.get(item.0)) {
+            let mut view = cb(value);
+            let width = self.scroll_core.last_available_size().x;
+            let height = view.required_size(Vec2::new(width, self.page_height())).y;
+            self.details.insert(item.0, DetailRow { view, height });
+            self.needs_relayout = true;
+        }
     }
 
-    /// Returns a mutable reference to the items contained within the table.
-    ///
-    /// Can be used to modify the items in place.
-    pub fn borrow_items_mut(&mut self) -> &mut Vec<T> {
-        self.needs_relayout = true;
-        &mut self.items
+    /// Collapses the detail row of the item at the specified index within
+    /// the underlying storage vector.
+    pub fn collapse_detail(&mut self, item: ItemIndex) {
+        if self.details.remove(&item.0).is_some() {
+            self.needs_relayout = true;
+        }
     }
 
-    /// Returns the index of the currently selected item within the underlying
-    /// storage vector.
-    pub fn item(&self) -> Option<usize> {
-        if self.items.is_empty() {
-            None
+    /// Expands or collapses the detail row of the item at the specified
+    /// index within the underlying storage vector.
+    pub fn toggle_detail(&mut self, item: ItemIndex) {
+        if self.is_detail_expanded(item) {
+            self.collapse_detail(item);
         } else {
-            Some(self.rows_to_items[self.focus])
+            self.expand_detail(item);
         }
     }
 
-    /// Selects the item at the specified index within the underlying storage
-    /// vector.
-    pub fn set_selected_item(&mut self, item_index: usize) {
-        // TODO optimize the performance for very large item lists
-        if item_index < self.items.len() {
-            for (row, item) in self.rows_to_items.iter().enumerate() {
-                if *item == item_index {
-                    self.focus = row;
-                    self.scroll_core.scroll_to_y(row);
-                    break;
-                }
-            }
-        }
+    /// Returns `true` if the detail row of the item at the specified index
+    /// within the underlying storage vector is expanded.
+    pub fn is_detail_expanded(&self, item: ItemIndex) -> bool {
+        self.details.contains_key(&item.0)
     }
 
-    /// Selects the item at the specified index within the underlying storage
-    /// vector.
-    ///
-    /// Chainable variant.
-    pub fn selected_item(self, item_index: usize) -> Self {
-        self.with(|t| t.set_selected_item(item_index))
+    /// Collapses all detail rows.
+    pub fn collapse_all_details(&mut self) {
+        self.details.clear();
+        self.needs_relayout = true;
     }
 
-    /// Inserts a new item into the table.
+    /// Returns the index of the column whose embedded view receives the
+    /// events of the focused row, if any.
+    pub fn active_cell(&self) -> Option<usize> {
+        self.active_cell
+    }
+
+    /// Sets the index of the column whose embedded view receives the events
+    /// of the focused row.
     ///
-    /// The currently active sort order is preserved and will be applied to the
-    /// newly inserted item.
-    pub fn insert_item(&mut self, item: T) {
-        self.items.push(item);
-        // Here we know self.items.len() > 0
-        self.rows_to_items.push(self.items.len() - 1);
+    /// Columns without an embedded view are ignored.
+    pub fn set_active_cell(&mut self, column: Option<usize>) {
+        self.active_cell =
+            column.filter(|&column| self.columns.get(column).map_or(false, |c| c.view.is_some()));
+    }
 
-        if let Some((column, order)) = self.order() {
-            self.sort_by(column, order);
-        }
+    /// Recreates the embedded views of all visible rows during the next
+    /// layout, e.g. after the items were modified in place.
+    pub fn refresh_cell_views(&mut self) {
+        self.cell_views.clear();
         self.needs_relayout = true;
     }
 
-    /// Removes the item at the specified index within the underlying storage
-    /// vector and returns it.
-    pub fn remove_item(&mut self, item_index: usize) -> Option<T> {
-        if item_index < self.items.len() {
-            // Move the selection if the currently selected item gets removed
-            if let Some(selected_index) = self.item() {
-                if selected_index == item_index {
-                    self.focus_up(1);
-                }
-            }
-
-            // Remove the sorted reference to the item
-            self.rows_to_items.retain(|i| *i != item_index);
-
-            // Adjust remaining references
-            for ref_index in &mut self.rows_to_items {
-                if *ref_index > item_index {
-                    *ref_index -= 1;
-                }
-            }
-            self.needs_relayout = true;
+    /// Returns `true` if the checkbox of the item at the specified index
+    /// within the underlying storage vector is checked.
+    pub fn is_checked(&self, item: ItemIndex) -> bool {
+        self.checked.contains(&item.0)
+    }
 
-            // Remove actual item from the underlying storage
-            Some(self.items.remove(item_index))
+    /// Checks or unchecks the item at the specified index within the
+    /// underlying storage vector.
+    ///
+    /// This does not invoke the `on_check` callback.
+    pub fn set_checked(&mut self, item: ItemIndex, checked: bool) {
+        if checked && item.0 < self.items.len() {
+            self.checked.insert(item.0);
         } else {
-            None
+            self.checked.remove(&item.0);
         }
     }
 
-    /// Removes all items from the underlying storage and returns them.
-    pub fn take_items(&mut self) -> Vec<T> {
-        self.set_selected_row(0);
-        self.rows_to_items.clear();
-        self.needs_relayout = true;
-        self.items.drain(0..).collect()
+    /// Checks or unchecks all items which are currently shown as rows, just
+    /// like the checkbox in the header does.
+    ///
+    /// This does not invoke the `on_check` callback.
+    pub fn set_all_checked(&mut self, checked: bool) {
+        for item in self.row_items() {
+            self.set_checked(ItemIndex(item), checked);
+        }
     }
-}
 
-impl<T: TableViewItem<H>, H: Eq + Hash + Copy + Clone + 'static> TableView<T, H> {
-    fn draw_columns<C: Fn(&Printer, &TableColumn<H>)>(
-        &self,
-        printer: &Printer,
-        sep: &str,
-        callback: C,
-    ) {
-        let mut column_offset = 0;
-        let column_count = self.columns.len();
-        for (index, column) in self.columns.iter().enumerate() {
-            let printer = &printer.offset((column_offset, 0)).focused(true);
+    /// Returns the indices of all checked items within the underlying
+    /// storage vector, in ascending order.
+    pub fn checked_items(&self) -> Vec<ItemIndex> {
+        let mut items: Vec<_> = self.checked.iter().cloned().map(ItemIndex).collect();
+        items.sort();
+        items
+    }
 
-            callback(printer, column);
+    /// Sets a callback to be used when the user checks or unchecks an item
+    /// via a checkbox column.
+    ///
+    /// The index of the item within the underlying storage vector and
+    /// whether it is now checked are given to the callback. Toggling the
+    /// checkbox in the header invokes the callback once per changed item.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// table.set_on_check(|siv: &mut Cursive, item: ItemIndex, checked: bool| {
+    ///
+    /// });
+    /// ```
+    pub fn set_on_check<F>(&mut self, cb: F)
+    where
+        F: Fn(&mut Cursive, ItemIndex, bool) + 'static,
+    {
+        self.on_check = Some(Rc::new(cb));
+    }
 
-            if 1 + index < column_count {
-                printer.print((column.width + 1, 0), sep);
-            }
+    /// Sets a callback to be used when the user checks or unchecks an item
+    /// via a checkbox column.
+    ///
+    /// Chainable variant.
+    pub fn on_check<F>(self, cb: F) -> Self
+    where
+        F: Fn(&mut Cursive, ItemIndex, bool) + 'static,
+    {
+        self.with(|t| t.set_on_check(cb))
+    }
 
-            column_offset += column.width + 3;
-        }
+    /// Sets a callback to be used when the user scrolls the table, either
+    /// directly or by moving the focus.
+    ///
+    /// The new scroll offset is given to the callback. Scrolling the table
+    /// programmatically, e.g. via `set_scroll_offset`, does not call it, so
+    /// two tables can be scroll linked without causing a feedback loop.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// table.set_on_scroll(|siv: &mut Cursive, offset: usize| {
+    ///     siv.call_on_name("other", |other: &mut TableView<Foo, BasicColumn>| {
+    ///         other.set_scroll_offset(offset);
+    ///     });
+    /// });
+    /// ```
+    pub fn set_on_scroll<F>(&mut self, cb: F)
+    where
+        F: Fn(&mut Cursive, usize) + 'static,
+    {
+        self.on_scroll = Some(Rc::new(cb));
     }
 
-    fn sort_items(&mut self, column: H, order: Ordering) {
-        if !self.is_empty() {
-            let old_item = self.item();
+    /// Sets a callback to be used when the user scrolls the table.
+    ///
+    /// Chainable variant.
+    pub fn on_scroll<F>(self, cb: F) -> Self
+    where
+        F: Fn(&mut Cursive, usize) + 'static,
+    {
+        self.with(|t| t.set_on_scroll(cb))
+    }
 
-            let mut rows_to_items = self.rows_to_items.clone();
-            rows_to_items.sort_by(|a, b| {
-                if order == Ordering::Less {
-                    self.items[*a].cmp(&self.items[*b], column)
-                } else {
-                    self.items[*b].cmp(&self.items[*a], column)
-                }
-            });
-            self.rows_to_items = rows_to_items;
+    /// Sets a callback to be used when a context menu is requested for an
+    /// item, either by right clicking it or by pressing `<Shift+F10>` while
+    /// it is selected.
+    ///
+    /// The row, the index of the item within the underlying storage vector,
+    /// the column under the mouse (or the selected column) and the absolute
+    /// screen position of the clicked cell are given to the callback.
+    ///
+    /// Since cursive does not report a dedicated menu key, `<Shift+F10>`
+    /// serves as the keyboard shortcut.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// table.set_on_context_menu(|siv: &mut Cursive, row: RowIndex, index: ItemIndex,
+    ///                            column: BasicColumn, position: Vec2| {
+    ///
+    /// });
+    /// ```
+    pub fn set_on_context_menu<F>(&mut self, cb: F)
+    where
+        F: Fn(&mut Cursive, RowIndex, ItemIndex, H, Vec2) + 'static,
+    {
+        self.on_context_menu = Some(Rc::new(cb));
+    }
 
-            if let Some(old_item) = old_item {
-                self.set_selected_item(old_item);
-            }
-        }
+    /// Sets a callback to be used when a context menu is requested for an
+    /// item.
+    ///
+    /// Chainable variant.
+    pub fn on_context_menu<F>(self, cb: F) -> Self
+    where
+        F: Fn(&mut Cursive, RowIndex, ItemIndex, H, Vec2) + 'static,
+    {
+        self.with(|t| t.set_on_context_menu(cb))
     }
 
-    fn draw_item(&self, printer: &Printer, i: usize) {
-        self.draw_columns(printer, "┆ ", |printer, column| {
-            let value = self.items[self.rows_to_items[i]].to_column(column.column);
-            column.draw_row(printer, value.as_str());
+    /// Sets a menu which pops up at the clicked cell whenever a context menu
+    /// is requested for an item.
+    ///
+    /// The item is focused before the menu opens, so the menu callbacks can
+    /// look it up via [`focused_item`](#method.focused_item).
+    ///
+    /// This replaces any callback set via `set_on_context_menu`.
+    pub fn set_context_menu(&mut self, menu: MenuTree) {
+        let menu = Rc::new(menu);
+        self.set_on_context_menu(move |siv, _, _, _, position| {
+            let offset = position.signed() - siv.screen().offset();
+            siv.screen_mut()
+                .add_layer_at(Position::parent(offset), MenuPopup::new(Rc::clone(&menu)));
         });
     }
 
-    fn on_focus_change(&self) -> EventResult {
-        let row = self.row().unwrap();
-        let index = self.item().unwrap();
-        EventResult::Consumed(
-            self.on_select
-                .clone()
-                .map(|cb| Callback::from_fn(move |s| cb(s, row, index))),
-        )
+    /// Sets a menu which pops up at the clicked cell whenever a context menu
+    /// is requested for an item.
+    ///
+    /// Chainable variant.
+    pub fn context_menu(self, menu: MenuTree) -> Self {
+        self.with(|t| t.set_context_menu(menu))
     }
 
-    fn focus_up(&mut self, n: usize) {
-        self.focus -= cmp::min(self.focus, n);
+    /// Sets a callback to be used for all interactions with the table.
+    ///
+    /// The callback receives a [`TableEvent`](struct.TableEvent.html)
+    /// describing the kind of interaction along with the affected row, item
+    /// and column. It is called in addition to the more specific `on_sort`,
+    /// `on_submit_item` and `on_select_item` callbacks.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// table.set_on_event(|siv: &mut Cursive, event: TableEvent<BasicColumn>| {
+    ///     if event.kind == TableEventKind::RightClick {
+    ///
+    ///     }
+    /// });
+    /// ```
+    pub fn set_on_event<F>(&mut self, cb: F)
+    where
+        F: Fn(&mut Cursive, TableEvent<H>) + 'static,
+    {
+        self.on_event = Some(Rc::new(cb));
     }
 
-    fn focus_down(&mut self, n: usize) {
-        self.focus = cmp::min(self.focus + n, self.items.len().saturating_sub(1));
+    /// Sets a callback to be used for all interactions with the table.
+    ///
+    /// Chainable variant.
+    pub fn on_event<F>(self, cb: F) -> Self
+    where
+        F: Fn(&mut Cursive, TableEvent<H>) + 'static,
+    {
+        self.with(|t| t.set_on_event(cb))
     }
 
-    fn active_column(&self) -> usize {
-        self.columns.iter().position(|c| c.selected).unwrap_or(0)
+    /// Sets a callback to be used when `<Enter>` is pressed while an item
+    /// is selected.
+    #[deprecated(since = "0.13.0", note = "use `set_on_submit_item` instead")]
+    pub fn set_on_submit<F>(&mut self, cb: F)
+    where
+        F: Fn(&mut Cursive, usize, usize) + 'static,
+    {
+        self.set_on_submit_item(move |s, row, index| cb(s, row.0, index.0));
     }
 
-    fn column_cancel(&mut self) {
-        self.column_select = false;
-        for column in &mut self.columns {
-            column.selected = column.order != Ordering::Equal;
-        }
+    /// Sets a callback to be used when `<Enter>` is pressed while an item
+    /// is selected.
+    ///
+    /// Chainable variant.
+    #[deprecated(since = "0.13.0", note = "use `on_submit_item` instead")]
+    pub fn on_submit<F>(self, cb: F) -> Self
+    where
+        F: Fn(&mut Cursive, usize, usize) + 'static,
+    {
+        self.on_submit_item(move |s, row, index| cb(s, row.0, index.0))
     }
 
-    fn column_next(&mut self) -> bool {
-        let column = self.active_column();
-        if 1 + column < self.columns.len() {
-            self.columns[column].selected = false;
-            self.columns[column + 1].selected = true;
-            true
-        } else {
-            false
-        }
+    /// Sets a callback to be used when an item is selected.
+    #[deprecated(since = "0.13.0", note = "use `set_on_select_item` instead")]
+    pub fn set_on_select<F>(&mut self, cb: F)
+    where
+        F: Fn(&mut Cursive, usize, usize) + 'static,
+    {
+        self.set_on_select_item(move |s, row, index| cb(s, row.0, index.0));
     }
 
-    fn column_prev(&mut self) -> bool {
-        let column = self.active_column();
-        if column > 0 {
-            self.columns[column].selected = false;
-            self.columns[column - 1].selected = true;
-            true
-        } else {
-            false
-        }
+    /// Sets a callback to be used when an item is selected.
+    ///
+    /// Chainable variant.
+    #[deprecated(since = "0.13.0", note = "use `on_select_item` instead")]
+    pub fn on_select<F>(self, cb: F) -> Self
+    where
+        F: Fn(&mut Cursive, usize, usize) + 'static,
+    {
+        self.on_select_item(move |s, row, index| cb(s, row.0, index.0))
     }
 
-    fn column_select(&mut self) -> EventResult {
-        let next = self.active_column();
-        let column = self.columns[next].column;
-        let current = self
-            .columns
-            .iter()
-            .position(|c| c.order != Ordering::Equal)
-            .unwrap_or(0);
+    /// Enables or disables follow mode.
+    ///
+    /// While follow mode is enabled and the last row is focused, the focus
+    /// stays pinned to the bottom of the table when new items are inserted.
+    /// Moving the focus or scrolling up pauses following, moving the focus
+    /// back to the last row (e.g. by pressing `<End>`) resumes it.
+    pub fn set_follow(&mut self, follow: bool) {
+        self.follow = follow;
+    }
 
-        let order = if current != next {
-            self.columns[next].default_order
-        } else if self.columns[current].order == Ordering::Less {
-            Ordering::Greater
-        } else {
-            Ordering::Less
-        };
+    /// Enables or disables follow mode.
+    ///
+    /// Chainable variant.
+    pub fn follow(self, follow: bool) -> Self {
+        self.with(|t| t.set_follow(follow))
+    }
 
-        self.sort_by(column, order);
+    /// Returns `true` if the table is currently following newly inserted
+    /// items.
+    pub fn is_following(&self) -> bool {
+        self.follow && self.following
+    }
 
-        if self.on_sort.is_some() {
-            let c = &self.columns[self.active_column()];
-            let column = c.column;
-            let order = c.order;
+    /// Sets the maximum number of items kept in the table.
+    ///
+    /// Once the capacity is exceeded, the oldest items within the underlying
+    /// storage vector are removed, so the table never holds more than
+    /// `max_items`.
+    pub fn set_max_items(&mut self, max_items: Option<usize>) {
+        self.max_items = max_items;
+        self.evict_items();
+    }
 
-            let cb = self.on_sort.clone().unwrap();
-            EventResult::with_cb(move |s| cb(s, column, order))
-        } else {
-            EventResult::Consumed(None)
-        }
+    /// Sets the maximum number of items kept in the table.
+    ///
+    /// Chainable variant.
+    pub fn max_items(self, max_items: usize) -> Self {
+        self.with(|t| t.set_max_items(Some(max_items)))
     }
 
-    fn column_for_x(&self, mut x: usize) -> Option<usize> {
-        for (i, col) in self.columns.iter().enumerate() {
-            if x < col.width {
-                return Some(i);
-            }
-            x = x.saturating_sub(col.width + 1);
+    /// Sets for how long changed items are highlighted.
+    ///
+    /// The highlight fades out over the given duration. Passing `None`
+    /// disables change highlighting, which is the default.
+    ///
+    /// In order for the highlight to fade out without further input, the
+    /// application should enable `Cursive::set_autorefresh` or set a non-zero
+    /// `Cursive::set_fps`.
+    pub fn set_change_highlight(&mut self, duration: Option<Duration>) {
+        self.change_highlight = duration;
+        if duration.is_none() {
+            self.changes.clear();
         }
+    }
 
-        None
+    /// Sets for how long changed items are highlighted.
+    ///
+    /// Chainable variant.
+    pub fn change_highlight(self, duration: Duration) -> Self {
+        self.with(|t| t.set_change_highlight(Some(duration)))
     }
 
-    fn draw_content(&self, printer: &Printer) {
-        for i in 0..self.rows_to_items.len() {
-            let printer = printer.offset((0, i));
-            let color = if i == self.focus && self.enabled {
-                if !self.column_select && self.enabled && printer.focused {
-                    theme::ColorStyle::highlight()
-                } else {
-                    theme::ColorStyle::highlight_inactive()
-                }
-            } else {
-                theme::ColorStyle::primary()
-            };
+    /// Sets the style used to highlight changed items.
+    pub fn set_change_style(&mut self, style: theme::ColorStyle) {
+        self.change_style = style;
+    }
 
-            if i < self.items.len() {
-                printer.with_color(color, |printer| {
-                    self.draw_item(printer, i);
-                });
+    /// Sets the style used to highlight changed items.
+    ///
+    /// Chainable variant.
+    pub fn change_style(self, style: theme::ColorStyle) -> Self {
+        self.with(|t| t.set_change_style(style))
+    }
+
+    /// Highlights the item at the specified index within the underlying
+    /// storage vector as recently changed.
+    ///
+    /// Does nothing unless change highlighting has been enabled via
+    /// [`set_change_highlight`](#method.set_change_highlight).
+    pub fn mark_changed(&mut self, item: ItemIndex) {
+        if let Some(duration) = self.change_highlight {
+            if item.0 < self.items.len() {
+                self.changes
+                    .retain(|_, changed| changed.elapsed() < duration);
+                self.changes.insert(item.0, Instant::now());
             }
         }
     }
 
-    fn layout_content(&mut self, size: Vec2) {
+    /// Returns `true` if the table is in tree mode.
+    pub fn is_tree(&self) -> bool {
+        self.tree.is_some()
+    }
+
+    /// Expands the item at the specified index within the underlying storage
+    /// vector, showing its children in tree mode.
+    pub fn expand_item(&mut self, item: ItemIndex) {
+        if item.0 < self.items.len() && self.expanded.insert(item.0) {
+            self.refresh_rows(None);
+        }
+    }
+
+    /// Collapses the item at the specified index within the underlying storage
+    /// vector, hiding its children in tree mode.
+    ///
+    /// If the currently selected item gets hidden, the collapsed item is
+    /// selected instead.
+    pub fn collapse_item(&mut self, item: ItemIndex) {
+        if self.expanded.remove(&item.0) {
+            self.refresh_rows(Some(item.0));
+        }
+    }
+
+    /// Toggles whether the item at the specified index within the underlying
+    /// storage vector is expanded in tree mode.
+    pub fn toggle_item(&mut self, item: ItemIndex) {
+        if self.is_expanded(item) {
+            self.collapse_item(item);
+        } else {
+            self.expand_item(item);
+        }
+    }
+
+    /// Returns `true` if the item at the specified index within the underlying
+    /// storage vector is expanded in tree mode.
+    pub fn is_expanded(&self, item: ItemIndex) -> bool {
+        self.expanded.contains(&item.0)
+    }
+
+    /// Expands all items in tree mode.
+    pub fn expand_all(&mut self) {
+        self.expanded = (0..self.items.len()).collect();
+        self.refresh_rows(None);
+    }
+
+    /// Collapses all items in tree mode.
+    pub fn collapse_all(&mut self) {
+        self.expanded.clear();
+        self.refresh_rows(None);
+    }
+
+    /// Groups the items of the table by their value in the specified column.
+    ///
+    /// Each group is shown below a header row displaying the group's value
+    /// and the number of items within it. Groups are ordered by the grouping
+    /// column, while the active sort order is applied within each group.
+    ///
+    /// Header rows can be selected, but are not associated with an item. They
+    /// are collapsed with `<Left>`, expanded with `<Right>` and toggled with
+    /// `<Enter>` or a click. In column selection mode, pressing `g` groups by
+    /// the selected column, or removes the grouping if it is already active.
+    ///
+    /// Grouping is ignored while the table is in tree mode. Passing `None`
+    /// removes the grouping.
+    pub fn set_group_by(&mut self, column: Option<H>) {
+        let old_item = self.current_item();
+        self.group_by = column.filter(|column| self.column_indicies.contains_key(column));
+        if self.group_by.is_none() {
+            self.groups.clear();
+            self.row_groups.clear();
+            self.rows_to_items = (0..self.items.len()).collect();
+            self.focus = 0;
+        }
+        self.sort();
+        if let Some(old_item) = old_item {
+            self.select_item(old_item);
+        }
+        self.needs_relayout = true;
+    }
+
+    /// Groups the items of the table by their value in the specified column.
+    ///
+    /// Chainable variant.
+    pub fn group_by(self, column: H) -> Self {
+        self.with(|t| t.set_group_by(Some(column)))
+    }
+
+    /// Returns the column the items of the table are grouped by.
+    pub fn grouping(&self) -> Option<H> {
+        self.group_by.clone()
+    }
+
+    /// Collapses the group with the specified value, hiding its items.
+    pub fn collapse_group(&mut self, key: &str) {
+        if self.collapsed_groups.insert(key.to_string()) {
+            // Move the focus to the header if one of the group's items is selected
+            if let Some(row) = self.group_header_row(key) {
+                let end = self.row_groups[row + 1..]
+                    .iter()
+                    .position(Option::is_some)
+                    .map_or(self.row_groups.len(), |end| row + 1 + end);
+                if self.focus > row && self.focus < end {
+                    self.focus = row;
+                }
+            }
+            self.refresh_rows(None);
+        }
+    }
+
+    /// Expands the group with the specified value, showing its items.
+    pub fn expand_group(&mut self, key: &str) {
+        if self.collapsed_groups.remove(key) {
+            self.refresh_rows(None);
+        }
+    }
+
+    /// Toggles whether the group with the specified value is collapsed.
+    pub fn toggle_group(&mut self, key: &str) {
+        if self.is_group_collapsed(key) {
+            self.expand_group(key);
+        } else {
+            self.collapse_group(key);
+        }
+    }
+
+    /// Returns `true` if the group with the specified value is collapsed.
+    pub fn is_group_collapsed(&self, key: &str) -> bool {
+        self.collapsed_groups.contains(key)
+    }
+
+    /// Returns the value of the group whose header row is currently selected.
+    pub fn group(&self) -> Option<&str> {
+        self.group_at(self.focus)
+            .map(|group| self.groups[group].key.as_str())
+    }
+
+    /// Removes all items from this view.
+    pub fn clear(&mut self) {
+        self.items.clear();
+        self.forget_keys();
+        self.changes.clear();
+        self.expanded.clear();
+        self.details.clear();
+        self.cell_views.clear();
+        self.checked.clear();
+        self.clear_rows();
+        self.focus = 0;
+        self.following = true;
+        self.needs_relayout = true;
+    }
+
+    /// Returns the number of items in this table.
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Returns `true` if this table has no items.
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Returns the range of rows which were visible during the last layout.
+    ///
+    /// This can be used to lazily load the items around the viewport.
+    pub fn visible_rows(&self) -> Range<usize> {
+        let len = self.rows_to_items.len();
+        let top = self.scroll_offset();
+        let first = self.row_at_y(top).map_or(len, |(row, _)| row);
+        let last = match self.scroll_core.last_available_size().y {
+            0 => first,
+            height => self
+                .row_at_y(top + height - 1)
+                .map_or(len, |(row, _)| row + 1),
+        };
+        first..cmp::max(first, last)
+    }
+
+    /// Returns the first row which was visible during the last layout.
+    pub fn first_visible_row(&self) -> Option<RowIndex> {
+        let rows = self.visible_rows();
+        if rows.is_empty() {
+            None
+        } else {
+            Some(RowIndex(rows.start))
+        }
+    }
+
+    /// Returns the number of lines scrolled past the top of the table.
+    ///
+    /// This equals the number of rows unless detail rows are expanded.
+    pub fn scroll_offset(&self) -> usize {
+        self.scroll_core.content_viewport().top()
+    }
+
+    /// Scrolls the table so that `offset` lines are hidden above the
+    /// viewport, without moving the focus.
+    ///
+    /// The offset is limited so that the viewport stays filled. The focused
+    /// row is scrolled back into view on the next interaction with the
+    /// table.
+    pub fn set_scroll_offset(&mut self, offset: usize) {
+        let left = self.scroll_core.content_viewport().left();
+        self.scroll_core.set_offset((left, offset));
+    }
+
+    /// Scrolls the table so that `row` is visible at the position given by
+    /// `align`, without moving the focus.
+    pub fn scroll_to_row(&mut self, row: RowIndex, align: ScrollAlign) {
+        let height = self.scroll_core.last_available_size().y;
+        let row = self.row_y(cmp::min(row.0, self.rows_to_items.len().saturating_sub(1)));
+        let top = self.scroll_offset();
+        let offset = match align {
+            ScrollAlign::Top => row,
+            ScrollAlign::Center => row.saturating_sub(height / 2),
+            ScrollAlign::Bottom => (row + 1).saturating_sub(height),
+            ScrollAlign::Nearest if row < top => row,
+            ScrollAlign::Nearest if row >= top + height => (row + 1).saturating_sub(height),
+            ScrollAlign::Nearest => top,
+        };
+        self.set_scroll_offset(offset);
+    }
+
+    /// Returns the currently focused table row.
+    pub fn focused_row(&self) -> Option<RowIndex> {
+        self.current_row().map(RowIndex)
+    }
+
+    /// Returns the item of the currently focused table row.
+    ///
+    /// Returns `None` while a group header row is focused.
+    pub fn focused_item(&self) -> Option<ItemIndex> {
+        self.current_item().map(ItemIndex)
+    }
+
+    /// Moves the focus to the specified row.
+    ///
+    /// Does nothing if the row does not exist.
+    pub fn focus_row(&mut self, row: RowIndex) {
+        if row.0 < self.rows_to_items.len() {
+            self.select_row(row.0);
+        }
+    }
+
+    /// Moves the focus to the row displaying the specified item.
+    ///
+    /// Does nothing if the item is not currently displayed.
+    pub fn focus_item(&mut self, item: ItemIndex) {
+        self.select_item(item.0);
+    }
+
+    /// Returns the row currently displaying the specified item.
+    ///
+    /// Returns `None` if the item is hidden inside a collapsed group or tree
+    /// node.
+    pub fn row_for_item(&self, item: ItemIndex) -> Option<RowIndex> {
+        self.row_of_item(item.0).map(RowIndex)
+    }
+
+    /// Returns the item displayed by the specified row.
+    ///
+    /// Returns `None` for group header rows and rows out of bounds.
+    pub fn item_for_row(&self, row: RowIndex) -> Option<ItemIndex> {
+        if self.group_at(row.0).is_some() {
+            None
+        } else {
+            self.rows_to_items.get(row.0).cloned().map(ItemIndex)
+        }
+    }
+
+    /// Returns the index of the currently selected table row.
+    #[deprecated(since = "0.13.0", note = "use `focused_row` instead")]
+    pub fn row(&self) -> Option<usize> {
+        self.current_row()
+    }
+
+    /// Selects the row at the specified index.
+    #[deprecated(since = "0.13.0", note = "use `focus_row` instead")]
+    pub fn set_selected_row(&mut self, row_index: usize) {
+        self.select_row(row_index);
+    }
+
+    /// Selects the row at the specified index.
+    ///
+    /// Chainable variant.
+    #[deprecated(since = "0.13.0", note = "use `focus_row` instead")]
+    pub fn selected_row(self, row_index: usize) -> Self {
+        self.with(|t| t.select_row(row_index))
+    }
+
+    /// Sets the contained items of the table.
+    ///
+    /// The currently active sort order is preserved and will be applied to all
+    /// items.
+    pub fn set_items(&mut self, items: Vec<T>) {
+        self.items = items;
+        self.forget_keys();
+        self.changes.clear();
+        self.expanded.clear();
+        self.details.clear();
+        self.cell_views.clear();
+        self.checked.clear();
+        self.clear_rows();
+        self.rows_to_items.reserve(self.items.len());
+
+        for i in 0..self.items.len() {
+            self.rows_to_items.push(i);
+        }
+
+        if self.has_row_structure() {
+            self.refresh_rows(None);
+        } else if let Some((column, order)) = self.sort_column() {
+            self.sort_items(column, order);
+        }
+
+        if self.is_following() {
+            self.focus = 0;
+            self.evict_items();
+            self.follow_bottom();
+        } else {
+            self.select_row(0);
+            self.evict_items();
+        }
+        self.needs_relayout = true;
+    }
+
+    /// Sets the contained items of the table.
+    ///
+    /// The order of the items will be preserved even when the table is sorted.
+    ///
+    /// Chainable variant.
+    pub fn items(self, items: Vec<T>) -> Self {
+        self.with(|t| t.set_items(items))
+    }
+
+    /// Returns a immmutable reference to the item at the specified index
+    /// within the underlying storage vector.
+    pub fn borrow_item(&self, item: ItemIndex) -> Option<&T> {
+        self.items.get(item.0)
+    }
+
+    /// Returns a mutable reference to the item at the specified index within
+    /// the underlying storage vector.
+    pub fn borrow_item_mut(&mut self, item: ItemIndex) -> Option<&mut T> {
+        self.needs_relayout = true;
+        self.forget_keys();
+        self.items.get_mut(item.0)
+    }
+
+    /// Returns a immmutable reference to the items contained within the table.
+    pub fn borrow_items(&mut self) -> &Vec<T> {
+        &self.items
+    }
+
+    /// Returns a mutable reference to the items contained within the table.
+    ///
+    /// Can be used to modify the items in place.
+    pub fn borrow_items_mut(&mut self) -> &mut Vec<T> {
+        self.needs_relayout = true;
+        self.forget_keys();
+        &mut self.items
+    }
+
+    /// Returns the index of the currently selected item within the underlying
+    /// storage vector.
+    ///
+    /// Returns `None` while a group header row is selected.
+    #[deprecated(since = "0.13.0", note = "use `focused_item` instead")]
+    pub fn item(&self) -> Option<usize> {
+        self.current_item()
+    }
+
+    /// Selects the item at the specified index within the underlying storage
+    /// vector.
+    #[deprecated(since = "0.13.0", note = "use `focus_item` instead")]
+    pub fn set_selected_item(&mut self, item_index: usize) {
+        self.select_item(item_index);
+    }
+
+    /// Selects the item at the specified index within the underlying storage
+    /// vector.
+    ///
+    /// Chainable variant.
+    #[deprecated(since = "0.13.0", note = "use `focus_item` instead")]
+    pub fn selected_item(self, item_index: usize) -> Self {
+        self.with(|t| t.select_item(item_index))
+    }
+
+    /// Inserts a new item into the table.
+    ///
+    /// The currently active sort order is preserved and will be applied to the
+    /// newly inserted item.
+    pub fn insert_item(&mut self, item: T) {
+        let following = self.is_following();
+
+        self.items.push(item);
+        // Here we know self.items.len() > 0
+        let index = self.items.len() - 1;
+        if let Some(ref mut keys) = *self.key_index.get_mut() {
+            keys.insert(&self.items[index], index);
+        }
+
+        if self.has_row_structure() {
+            self.refresh_rows(None);
+            self.evict_items();
+            if following {
+                self.follow_bottom();
+            }
+            self.needs_relayout = true;
+            return;
+        }
+
+        let row = match self.sort_column() {
+            Some((column, order)) => {
+                let compare = Self::compare_items(&self.items, &self.columns, column, order);
+                self.rows_to_items
+                    .partition_point(|i| compare(i, &index) != Ordering::Greater)
+            }
+            None => self.rows_to_items.len(),
+        };
+        self.rows_to_items.insert(row, index);
+
+        // Keep the focus on the previously selected item
+        if row <= self.focus && self.rows_to_items.len() > 1 {
+            self.focus += 1;
+        }
+
+        self.evict_items();
+        if following {
+            self.follow_bottom();
+        }
+        self.needs_relayout = true;
+    }
+
+    /// Removes the item at the specified index within the underlying storage
+    /// vector and returns it.
+    pub fn remove_item(&mut self, item: ItemIndex) -> Option<T> {
+        let item_index = item.0;
+        if item_index < self.items.len() && self.has_row_structure() {
+            let removed = item_index..item_index + 1;
+            let focused = self
+                .current_item()
+                .and_then(|index| shift_index(index, &removed));
+
+            let item = self.items.remove(item_index);
+            self.forget_changes(removed);
+            self.clear_rows();
+            self.refresh_rows(focused);
+            Some(item)
+        } else if item_index < self.items.len() {
+            // Move the selection if the currently selected item or a row above
+            // it gets removed
+            if let Some(row) = self.row_of_item(item_index) {
+                if row <= self.focus {
+                    self.focus_up(1);
+                }
+            }
+
+            // Remove the sorted reference to the item
+            self.rows_to_items.retain(|i| *i != item_index);
+
+            // Adjust remaining references
+            for ref_index in &mut self.rows_to_items {
+                if *ref_index > item_index {
+                    *ref_index -= 1;
+                }
+            }
+            self.forget_changes(item_index..item_index + 1);
+            self.needs_relayout = true;
+
+            // Remove actual item from the underlying storage
+            Some(self.items.remove(item_index))
+        } else {
+            None
+        }
+    }
+
+    /// Removes all items from the underlying storage and returns them.
+    pub fn take_items(&mut self) -> Vec<T> {
+        self.select_row(0);
+        self.clear_rows();
+        self.changes.clear();
+        self.expanded.clear();
+        self.details.clear();
+        self.cell_views.clear();
+        self.checked.clear();
+        self.following = true;
+        self.needs_relayout = true;
+        self.forget_keys();
+        self.items.drain(0..).collect()
+    }
+}
+
+impl<T: TreeTableItem<H>, H: Eq + Hash + Clone + MaybeSync + 'static> TableView<T, H> {
+    /// Enables or disables tree mode.
+    ///
+    /// In tree mode only top level items and the children of expanded items
+    /// are shown, with the first column indicating the depth of each item.
+    /// Sorting is applied within each group of siblings.
+    ///
+    /// `<Right>` expands and `<Left>` collapses the selected item; `<Left>`
+    /// on a collapsed item selects its parent. `<Enter>` toggles an item
+    /// unless a submit callback is set.
+    pub fn set_tree(&mut self, enabled: bool) {
+        if enabled {
+            self.tree = Some(Self::tree_parents);
+            self.refresh_rows(None);
+        } else {
+            self.tree = None;
+            self.tree_rows.clear();
+            self.rows_to_items = (0..self.items.len()).collect();
+            self.focus = 0;
+            self.sort();
+        }
+    }
+
+    /// Enables tree mode.
+    ///
+    /// Chainable variant.
+    pub fn tree(self) -> Self {
+        self.with(|t| t.set_tree(true))
+    }
+
+    fn tree_parents(items: &[T]) -> Vec<Option<usize>> {
+        let indices: HashMap<T::Key, usize> = items
+            .iter()
+            .enumerate()
+            .map(|(index, item)| (item.key(), index))
+            .collect();
+
+        items
+            .iter()
+            .map(|item| item.parent().and_then(|key| indices.get(&key).cloned()))
+            .collect()
+    }
+}
+
+impl<T: KeyedTableViewItem<H>, H: Eq + Hash + Clone + MaybeSync + 'static> TableView<T, H> {
+    /// Returns the index of the item with the specified `key` within the
+    /// underlying storage vector.
+    ///
+    /// The keys of all items are indexed on the first lookup, so repeated
+    /// lookups do not need to scan the items.
+    pub fn find_by_key(&self, key: &T::Key) -> Option<ItemIndex> {
+        self.index_of_key(key).map(ItemIndex)
+    }
+
+    fn index_of_key(&self, key: &T::Key) -> Option<usize> {
+        let mut keys = self.key_index.borrow_mut();
+        let keys = keys.get_or_insert_with(|| KeyIndex::new(hash_item::<T, H>, &self.items));
+        keys.buckets
+            .get(&hash_key(key))?
+            .iter()
+            .cloned()
+            .find(|&index| self.items[index].key() == *key)
+    }
+
+    /// Inserts an item into the table or replaces the existing item with the
+    /// same key, returning the replaced item.
+    ///
+    /// The currently active sort order is preserved and the focus stays on
+    /// the currently selected item.
+    pub fn upsert_item(&mut self, item: T) -> Option<T> {
+        let index = match self.index_of_key(&item.key()) {
+            Some(index) => index,
+            None => {
+                self.insert_item(item);
+                // New items are always appended, evictions only happen at
+                // the front
+                if let Some(index) = self.items.len().checked_sub(1) {
+                    self.mark_changed(ItemIndex(index));
+                }
+                return None;
+            }
+        };
+
+        let row = if self.has_row_structure() {
+            None
+        } else {
+            self.row_of_sorted_item(index)
+        };
+        let old = std::mem::replace(&mut self.items[index], item);
+
+        match (row, self.sort_column()) {
+            (Some(row), Some((column, order))) => {
+                // Move only the updated row to its new sorted position
+                self.rows_to_items.remove(row);
+                let compare = Self::compare_items(&self.items, &self.columns, column, order);
+                let new_row = self
+                    .rows_to_items
+                    .partition_point(|i| compare(i, &index) != Ordering::Greater);
+                self.rows_to_items.insert(new_row, index);
+
+                if self.focus == row {
+                    self.focus = new_row;
+                } else {
+                    if row < self.focus {
+                        self.focus -= 1;
+                    }
+                    if new_row <= self.focus {
+                        self.focus += 1;
+                    }
+                }
+            }
+            _ if self.has_row_structure() => self.refresh_rows(None),
+            _ => {}
+        }
+
+        self.mark_changed(ItemIndex(index));
+        self.needs_relayout = true;
+        Some(old)
+    }
+
+    /// Removes the item with the specified `key` and returns it.
+    pub fn remove_by_key(&mut self, key: &T::Key) -> Option<T> {
+        self.find_by_key(key)
+            .and_then(|index| self.remove_item(index))
+    }
+
+    /// Replaces the contained items of the table.
+    ///
+    /// Unlike [`set_items`](#method.set_items), the focus stays on the item
+    /// with the same key as the previously selected one and the scroll
+    /// position is kept relative to it. If that item no longer exists, the
+    /// focus stays on the same row instead.
+    ///
+    /// Expanded tree items, checked items and expanded detail rows stay so if
+    /// they still exist.
+    /// With change highlighting enabled, new items and items whose column
+    /// values differ from their previous version are marked as changed.
+    pub fn replace_items(&mut self, items: Vec<T>) {
+        let focused = self
+            .current_item()
+            .map(|index| (self.items[index].key(), self.focus));
+        let top = self.scroll_core.content_viewport().top();
+
+        let expanded: HashSet<T::Key> = self
+            .expanded
+            .iter()
+            .map(|index| self.items[*index].key())
+            .collect();
+        let checked: HashSet<T::Key> = self
+            .checked
+            .iter()
+            .map(|index| self.items[*index].key())
+            .collect();
+        let details: HashSet<T::Key> = self
+            .details
+            .keys()
+            .map(|index| self.items[*index].key())
+            .collect();
+
+        let previous: HashMap<T::Key, Vec<String>> = match self.change_highlight {
+            Some(_) => self
+                .items
+                .iter()
+                .map(|item| (item.key(), self.column_values(item)))
+                .collect(),
+            None => HashMap::new(),
+        };
+
+        self.set_items(items);
+
+        if !expanded.is_empty() {
+            self.expanded = self
+                .items
+                .iter()
+                .enumerate()
+                .filter(|(_, item)| expanded.contains(&item.key()))
+                .map(|(index, _)| index)
+                .collect();
+            self.refresh_rows(None);
+        }
+
+        if !checked.is_empty() {
+            self.checked = self
+                .items
+                .iter()
+                .enumerate()
+                .filter(|(_, item)| checked.contains(&item.key()))
+                .map(|(index, _)| index)
+                .collect();
+        }
+
+        // Detail views are created anew, so they show the replaced items
+        if !details.is_empty() {
+            for index in 0..self.items.len() {
+                if details.contains(&self.items[index].key()) {
+                    self.expand_detail(ItemIndex(index));
+                }
+            }
+        }
+
+        if !previous.is_empty() {
+            let changed: Vec<usize> = self
+                .items
+                .iter()
+                .enumerate()
+                .filter(|(_, item)| {
+                    previous
+                        .get(&item.key())
+                        .map_or(true, |values| *values != self.column_values(item))
+                })
+                .map(|(index, _)| index)
+                .collect();
+
+            let now = Instant::now();
+            for index in changed {
+                self.changes.insert(index, now);
+            }
+        }
+
+        if let Some((key, row)) = focused {
+            self.focus = match self.index_of_key(&key) {
+                Some(index) => self.row_of_item(index).unwrap_or(0),
+                None => cmp::min(row, self.rows_to_items.len().saturating_sub(1)),
+            };
+            self.set_scroll_top(self.focus.saturating_sub(row.saturating_sub(top)));
+        }
+    }
+}
+
+impl<T: TableViewItem<H>, H: Eq + Hash + Clone + MaybeSync + 'static> TableView<T, H> {
+    fn current_row(&self) -> Option<usize> {
+        if self.rows_to_items.is_empty() {
+            None
+        } else {
+            Some(self.focus)
+        }
+    }
+
+    fn current_item(&self) -> Option<usize> {
+        self.item_for_row(RowIndex(self.focus)).map(|item| item.0)
+    }
+
+    fn select_row(&mut self, row_index: usize) {
+        self.focus = row_index;
+        self.scroll_core.scroll_to_y(self.row_y(row_index));
+        self.update_following();
+    }
+
+    fn select_item(&mut self, item_index: usize) {
+        if let Some(row) = self.row_of_item(item_index) {
+            self.select_row(row);
+        }
+    }
+
+    fn column_values(&self, item: &T) -> Vec<String> {
+        self.columns
+            .iter()
+            .map(|column| column.text(item))
+            .collect()
+    }
+
+    fn row_of_item(&self, item_index: usize) -> Option<usize> {
+        self.rows_to_items
+            .iter()
+            .position(|item| *item == item_index)
+    }
+
+    /// Finds the row of an item via binary search within the rows sorted by
+    /// the current sort column.
+    fn row_of_sorted_item(&self, item_index: usize) -> Option<usize> {
+        let (column, order) = match self.sort_column() {
+            Some(sort) => sort,
+            None => return self.row_of_item(item_index),
+        };
+        let compare = Self::compare_items(&self.items, &self.columns, column, order);
+        let start = self
+            .rows_to_items
+            .partition_point(|i| compare(i, &item_index) == Ordering::Less);
+        self.rows_to_items[start..]
+            .iter()
+            .take_while(|i| compare(i, &item_index) == Ordering::Equal)
+            .position(|i| *i == item_index)
+            .map(|row| start + row)
+            // Items modified in place may no longer be in sorted order
+            .or_else(|| self.row_of_item(item_index))
+    }
+
+    fn update_following(&mut self) {
+        self.following = self.focus + 1 >= self.rows_to_items.len();
+    }
+
+    fn follow_bottom(&mut self) {
+        self.focus = self.rows_to_items.len().saturating_sub(1);
+        self.scroll_core.scroll_to_y(self.row_y(self.focus));
+        self.following = true;
+    }
+
+    fn focused_tree_row(&self) -> Option<(usize, TreeRow)> {
+        let row = *self.tree_rows.get(self.focus)?;
+        Some((self.rows_to_items[self.focus], row))
+    }
+
+    fn has_checkboxes(&self) -> bool {
+        self.columns.iter().any(|column| column.checkbox)
+    }
+
+    /// Returns the items of all rows, skipping group headers.
+    fn row_items(&self) -> Vec<usize> {
+        (0..self.rows_to_items.len())
+            .filter_map(|row| self.item_for_row(RowIndex(row)).map(usize::from))
+            .collect()
+    }
+
+    /// Returns the item whose checkbox is at `position`, if any.
+    fn checkbox_at(&self, position: Vec2) -> Option<usize> {
+        let item = self.item_for_row(RowIndex(position.y))?;
+        (0..self.columns.len())
+            .find(|&column| {
+                let x = self.column_x(column);
+                self.columns[column].checkbox
+                    && position.x >= x
+                    && position.x < x + self.columns[column].width
+            })
+            .map(|_| item.0)
+    }
+
+    fn check_items(&mut self, items: Vec<usize>, checked: bool) -> EventResult {
+        let changed: Vec<usize> = items
+            .into_iter()
+            .filter(|item| self.checked.contains(item) != checked)
+            .collect();
+        for &item in &changed {
+            self.set_checked(ItemIndex(item), checked);
+        }
+
+        match self.on_check.clone() {
+            Some(cb) if !changed.is_empty() => EventResult::with_cb(move |s| {
+                for &item in &changed {
+                    cb(s, ItemIndex(item), checked);
+                }
+            }),
+            _ => EventResult::Consumed(None),
+        }
+    }
+
+    fn has_cell_views(&self) -> bool {
+        self.columns.iter().any(|column| column.view.is_some())
+    }
+
+    /// Returns the horizontal offset of the column at `index`.
+    fn column_x(&self, index: usize) -> usize {
+        self.columns[..index]
+            .iter()
+            .map(|column| column.width + 3)
+            .sum()
+    }
+
+    /// Returns the column of the embedded view at `position`, if any.
+    fn cell_view_at(&self, position: Vec2) -> Option<usize> {
+        let item = self.item_for_row(RowIndex(position.y))?;
+        (0..self.columns.len()).find(|&column| {
+            let x = self.column_x(column);
+            position.x >= x
+                && position.x < x + self.columns[column].width
+                && self.cell_views.contains_key(&(item.0, column))
+        })
+    }
+
+    fn on_cell_event(&mut self, row: usize, column: usize, event: Event) -> EventResult {
+        let offset = Vec2::new(self.column_x(column), row);
+        match self
+            .item_for_row(RowIndex(row))
+            .and_then(|item| self.cell_views.get_mut(&(item.0, column)))
+        {
+            Some(view) => view.on_event(event.relativized(offset)),
+            None => EventResult::Ignored,
+        }
+    }
+
+    /// Creates the embedded views of newly visible rows and drops the ones
+    /// of rows which were scrolled out of view.
+    fn update_cell_views(&mut self) {
+        if !self.has_cell_views() {
+            self.cell_views.clear();
+            return;
+        }
+
+        let items: HashSet<usize> = self
+            .visible_rows()
+            .filter_map(|row| self.item_for_row(RowIndex(row)).map(usize::from))
+            .collect();
+        self.cell_views.retain(|(item, _), _| items.contains(item));
+
+        let values = &self.items;
+        for (index, column) in self.columns.iter().enumerate() {
+            if let Some(ref cb) = column.view {
+                for &item in &items {
+                    self.cell_views
+                        .entry((item, index))
+                        .or_insert_with(|| cb(&values[item]))
+                        .layout(Vec2::new(column.width, 1));
+                }
+            }
+        }
+    }
+
+    fn detail_marker_at(&self, position: Vec2) -> Option<usize> {
+        if self.detail_view.is_some() && position.x < 2 {
+            self.item_for_row(RowIndex(position.y)).map(usize::from)
+        } else {
+            None
+        }
+    }
+
+    fn detail_height(&self, row: usize) -> usize {
+        self.item_for_row(RowIndex(row))
+            .and_then(|item| self.details.get(&item.0))
+            .map_or(0, |detail| detail.height)
+    }
+
+    /// Returns the line of the content on which `row` is drawn.
+    fn row_y(&self, row: usize) -> usize {
+        if self.details.is_empty() {
+            row
+        } else {
+            row + (0..row).map(|row| self.detail_height(row)).sum::<usize>()
+        }
+    }
+
+    /// Returns the row drawn at line `y` of the content, along with the line
+    /// within its detail row if `y` lies inside of it.
+    fn row_at_y(&self, y: usize) -> Option<(usize, Option<usize>)> {
+        let len = self.rows_to_items.len();
+        if self.details.is_empty() {
+            return if y < len { Some((y, None)) } else { None };
+        }
+
+        let mut top = 0;
+        for row in 0..len {
+            let height = self.detail_height(row);
+            if y == top {
+                return Some((row, None));
+            } else if y <= top + height {
+                return Some((row, Some(y - top - 1)));
+            }
+            top += 1 + height;
+        }
+        None
+    }
+
+    fn on_detail_event(&mut self, row: usize, event: Event) -> EventResult {
+        let y = self.row_y(row) + 1;
+        match self
+            .item_for_row(RowIndex(row))
+            .and_then(|item| self.details.get_mut(&item.0))
+        {
+            Some(detail) => detail.view.on_event(event.relativized((0, y))),
+            None => EventResult::Ignored,
+        }
+    }
+
+    fn tree_marker_at(&self, position: Vec2) -> Option<usize> {
+        let row = self.tree_rows.get(position.y)?;
+        let x = row.depth * 2 + if self.detail_view.is_some() { 2 } else { 0 };
+        if row.has_children && position.x >= x && position.x < x + 2 {
+            Some(self.rows_to_items[position.y])
+        } else {
+            None
+        }
+    }
+
+    fn has_footer(&self) -> bool {
+        self.columns.iter().any(|column| column.aggregate.is_some())
+    }
+
+    fn update_footer(&mut self) {
+        if self.has_footer() {
+            let items: Vec<&T> = self.items.iter().collect();
+            let footers: Vec<String> = self
+                .columns
+                .iter()
+                .map(|column| match column.aggregate {
+                    Some(ref aggregate) => aggregate.apply(&items, column),
+                    None => String::new(),
+                })
+                .collect();
+
+            for (column, footer) in self.columns.iter_mut().zip(footers) {
+                column.footer = footer;
+            }
+        }
+    }
+
+    fn clear_rows(&mut self) {
+        self.rows_to_items.clear();
+        self.tree_rows.clear();
+        self.groups.clear();
+        self.row_groups.clear();
+    }
+
+    fn sort_column(&self) -> Option<(usize, Ordering)> {
+        self.columns
+            .iter()
+            .position(|c| c.order != Ordering::Equal)
+            .map(|index| (index, self.columns[index].order))
+    }
+
+    fn grouping_column(&self) -> Option<usize> {
+        let column = self.group_by.as_ref()?;
+        self.column_indicies.get(column).cloned()
+    }
+
+    fn has_row_structure(&self) -> bool {
+        self.tree.is_some() || self.group_by.is_some()
+    }
+
+    fn group_at(&self, row: usize) -> Option<usize> {
+        self.row_groups.get(row).cloned().and_then(|group| group)
+    }
+
+    fn group_header_row(&self, key: &str) -> Option<usize> {
+        self.row_groups
+            .iter()
+            .position(|group| group.map_or(false, |group| self.groups[group].key == key))
+    }
+
+    /// Rebuilds the visible rows from the expanded nodes of the tree or the
+    /// groups of items.
+    ///
+    /// The focus stays on the currently selected item or group header, if it
+    /// is hidden the `fallback` item is selected instead.
+    fn refresh_rows(&mut self, fallback: Option<usize>) {
+        let old_item = self.current_item();
+        let old_group = self.group().map(str::to_string);
+
+        if let Some(parents) = self.tree {
+            let parents = parents(&self.items);
+            self.flatten_tree(parents);
+        } else if let Some(column) = self.grouping_column() {
+            self.group_items(column);
+        } else {
+            return;
+        }
+
+        self.focus = match old_item
+            .and_then(|index| self.row_of_item(index))
+            .or_else(|| old_group.and_then(|key| self.group_header_row(&key)))
+            .or_else(|| fallback.and_then(|index| self.row_of_item(index)))
+        {
+            Some(row) => row,
+            None => cmp::min(self.focus, self.rows_to_items.len().saturating_sub(1)),
+        };
+        self.scroll_core.scroll_to_y(self.row_y(self.focus));
+        self.needs_relayout = true;
+    }
+
+    fn flatten_tree(&mut self, parents: Vec<Option<usize>>) {
+        let mut roots = Vec::new();
+        let mut children = vec![Vec::new(); self.items.len()];
+        for (index, parent) in parents.into_iter().enumerate() {
+            match parent {
+                Some(parent) if parent != index => children[parent].push(index),
+                _ => roots.push(index),
+            }
+        }
+
+        // Sort within each group of siblings
+        if let Some((column, order)) = self.sort_column() {
+            let compare = Self::compare_items(&self.items, &self.columns, column, order);
+            roots.sort_by(&compare);
+            for siblings in &mut children {
+                siblings.sort_by(&compare);
+            }
+        }
+
+        self.rows_to_items.clear();
+        self.tree_rows.clear();
+
+        let mut stack: Vec<(usize, TreeRow)> = roots
+            .into_iter()
+            .rev()
+            .map(|index| {
+                (
+                    index,
+                    TreeRow {
+                        depth: 0,
+                        parent: None,
+                        has_children: false,
+                    },
+                )
+            })
+            .collect();
+
+        while let Some((index, mut row)) = stack.pop() {
+            row.has_children = !children[index].is_empty();
+            self.rows_to_items.push(index);
+            self.tree_rows.push(row);
+
+            if self.expanded.contains(&index) {
+                stack.extend(children[index].iter().rev().map(|child| {
+                    (
+                        *child,
+                        TreeRow {
+                            depth: row.depth + 1,
+                            parent: Some(index),
+                            has_children: false,
+                        },
+                    )
+                }));
+            }
+        }
+    }
+
+    fn group_items(&mut self, column: usize) {
+        let mut rows: Vec<usize> = (0..self.items.len()).collect();
+        if let Some((column, order)) = self.sort_column() {
+            rows.sort_by(Self::compare_items(
+                &self.items,
+                &self.columns,
+                column,
+                order,
+            ));
+        }
+
+        let mut groups: Vec<(String, Vec<usize>)> = Vec::new();
+        let mut indices: HashMap<String, usize> = HashMap::new();
+        for index in rows {
+            let key = self.columns[column].text(&self.items[index]);
+            let group = *indices.entry(key.clone()).or_insert_with(|| {
+                groups.push((key, Vec::new()));
+                groups.len() - 1
+            });
+            groups[group].1.push(index);
+        }
+
+        // Order the groups themselves by the grouping column
+        let order = match self.sort_column() {
+            Some((c, order)) if c == column => order,
+            _ => Ordering::Less,
+        };
+        let compare = Self::compare_items(&self.items, &self.columns, column, order);
+        groups.sort_by(|a, b| compare(&a.1[0], &b.1[0]));
+
+        self.rows_to_items.clear();
+        self.row_groups.clear();
+        self.groups.clear();
+
+        for (key, members) in groups {
+            self.rows_to_items.push(GROUP_ROW);
+            self.row_groups.push(Some(self.groups.len()));
+
+            if !self.collapsed_groups.contains(&key) {
+                for index in &members {
+                    self.rows_to_items.push(*index);
+                    self.row_groups.push(None);
+                }
+            }
+
+            self.groups.push(TableGroup {
+                key,
+                count: members.len(),
+            });
+        }
+    }
+
+    fn compare_items<'a>(
+        items: &'a [T],
+        columns: &'a [TableColumn<T, H>],
+        column: usize,
+        order: Ordering,
+    ) -> impl Fn(&usize, &usize) -> Ordering + 'a {
+        // Only capture what is needed, so the closure stays `Sync` for rayon
+        let compare = columns[column].compare.as_deref();
+        let column = &columns[column].column;
+
+        // Empty cells stay last regardless of the sort order, which requires
+        // to know whether a cell is empty when sorting in descending order.
+        // This is determined lazily and at most once per item, atomics keep
+        // the closure `Sync`.
+        let empty: Vec<AtomicU8> = if order != Ordering::Less && compare.is_none() {
+            items.iter().map(|_| AtomicU8::new(EMPTY_UNKNOWN)).collect()
+        } else {
+            Vec::new()
+        };
+        let is_empty = move |index: usize| match empty[index].load(AtomicOrdering::Relaxed) {
+            EMPTY_UNKNOWN => {
+                let is_empty = items[index].cell(column.clone()) == CellValue::Empty;
+                empty[index].store(is_empty as u8, AtomicOrdering::Relaxed);
+                is_empty
+            }
+            state => state == 1,
+        };
+
+        move |a: &usize, b: &usize| {
+            let ordering = match compare {
+                Some(compare) => compare(&items[*a], &items[*b]),
+                None => items[*a].cmp(&items[*b], column.clone()),
+            };
+
+            if order == Ordering::Less || ordering == Ordering::Equal {
+                return ordering;
+            }
+
+            if compare.is_none() {
+                match (is_empty(*a), is_empty(*b)) {
+                    (true, false) => return Ordering::Greater,
+                    (false, true) => return Ordering::Less,
+                    _ => {}
+                }
+            }
+            ordering.reverse()
+        }
+    }
+
+    fn evict_items(&mut self) {
+        let excess = match self.max_items {
+            Some(max_items) => self.items.len().saturating_sub(max_items),
+            None => 0,
+        };
+
+        if excess > 0 {
+            let focused = self.current_item();
+
+            // Oldest items are at the front of the storage vector
+            self.items.drain(0..excess);
+            self.rows_to_items.retain(|i| *i >= excess);
+            for index in &mut self.rows_to_items {
+                *index -= excess;
+            }
+            self.forget_changes(0..excess);
+            if self.has_row_structure() {
+                self.clear_rows();
+                self.refresh_rows(None);
+            }
+
+            self.focus = match focused {
+                Some(index) if index >= excess => self.row_of_item(index - excess).unwrap_or(0),
+                _ => cmp::min(self.focus, self.rows_to_items.len().saturating_sub(1)),
+            };
+            self.needs_relayout = true;
+        }
+    }
+
+    fn forget_keys(&mut self) {
+        *self.key_index.get_mut() = None;
+    }
+
+    fn forget_changes(&mut self, removed: Range<usize>) {
+        self.forget_keys();
+
+        if !self.changes.is_empty() {
+            self.changes = self
+                .changes
+                .drain()
+                .filter_map(|(index, changed)| {
+                    shift_index(index, &removed).map(|index| (index, changed))
+                })
+                .collect();
+        }
+
+        if !self.expanded.is_empty() {
+            self.expanded = self
+                .expanded
+                .drain()
+                .filter_map(|index| shift_index(index, &removed))
+                .collect();
+        }
+
+        if !self.details.is_empty() {
+            self.details = self
+                .details
+                .drain()
+                .filter_map(|(index, detail)| {
+                    shift_index(index, &removed).map(|index| (index, detail))
+                })
+                .collect();
+        }
+
+        if !self.checked.is_empty() {
+            self.checked = self
+                .checked
+                .drain()
+                .filter_map(|index| shift_index(index, &removed))
+                .collect();
+        }
+
+        if !self.cell_views.is_empty() {
+            self.cell_views = self
+                .cell_views
+                .drain()
+                .filter_map(|((index, column), view)| {
+                    shift_index(index, &removed).map(|index| ((index, column), view))
+                })
+                .collect();
+        }
+    }
+
+    fn change_color(&self, printer: &Printer, item_index: usize) -> Option<theme::ColorStyle> {
+        let duration = self.change_highlight?;
+        let age = self.changes.get(&item_index)?.elapsed();
+        if age >= duration {
+            return None;
+        }
+
+        // Fade the highlight towards the regular background over time
+        let progress = age.as_secs_f32() / duration.as_secs_f32();
+        let primary = theme::ColorStyle::primary();
+        let front = if progress < 0.5 {
+            self.change_style.front
+        } else {
+            primary.front
+        };
+
+        let palette = &printer.theme.palette;
+        let from = self
+            .change_style
+            .back
+            .resolve(palette, theme::Color::TerminalDefault);
+        let to = primary.back.resolve(palette, theme::Color::TerminalDefault);
+        let back = match (rgb(from), rgb(to)) {
+            (Some(from), Some(to)) => {
+                let mix = |a: u8, b: u8| (a as f32 + (b as f32 - a as f32) * progress) as u8;
+                theme::ColorType::Color(theme::Color::Rgb(
+                    mix(from.0, to.0),
+                    mix(from.1, to.1),
+                    mix(from.2, to.2),
+                ))
+            }
+            _ => self.change_style.back,
+        };
+
+        Some(theme::ColorStyle { front, back })
+    }
+
+    fn set_scroll_top(&mut self, y: usize) {
+        let height = self.scroll_core.last_available_size().y;
+        self.scroll_core.scroll_to_top();
+        if height > 0 {
+            self.scroll_core.scroll_to_y(y + height - 1);
+        }
+    }
+
+    fn draw_columns<C: Fn(&Printer, &TableColumn<T, H>)>(
+        &self,
+        printer: &Printer,
+        sep: &str,
+        callback: C,
+    ) {
+        let mut column_offset = 0;
+        let column_count = self.columns.len();
+        for (index, column) in self.columns.iter().enumerate() {
+            let printer = &printer.offset((column_offset, 0)).focused(true);
+
+            callback(printer, column);
+
+            if 1 + index < column_count {
+                printer.print((column.width + 1, 0), sep);
+            }
+
+            column_offset += column.width + 3;
+        }
+    }
+
+    fn sort_items(&mut self, column: usize, order: Ordering) {
+        if self.has_row_structure() {
+            self.refresh_rows(None);
+        } else if !self.is_empty() {
+            let old_item = self.current_item();
+
+            let items = &self.items;
+            let compare = Self::compare_items(items, &self.columns, column, order);
+
+            #[cfg(feature = "rayon")]
+            {
+                if items.len() > PARALLEL_SORT_THRESHOLD {
+                    self.rows_to_items.par_sort_by(compare);
+                } else {
+                    self.rows_to_items.sort_by(compare);
+                }
+            }
+
+            #[cfg(not(feature = "rayon"))]
+            self.rows_to_items.sort_by(compare);
+
+            if let Some(old_item) = old_item {
+                self.select_item(old_item);
+            }
+        }
+    }
+
+    fn draw_item(&self, printer: &Printer, i: usize) {
+        let index = self.rows_to_items[i];
+        let tree_row = self.tree_rows.get(i);
+        self.draw_columns(printer, "┆ ", |printer, column| {
+            let cell = (index, self.column_indicies[&column.column]);
+            if let Some(view) = self.cell_views.get(&cell) {
+                let active = i == self.focus && self.active_cell == Some(cell.1);
+                printer.print_hline((0, 0), column.width + 1, " ");
+                return view.draw(
+                    &printer
+                        .cropped((column.width, 1))
+                        .focused(printer.focused && active && !self.column_select),
+                );
+            }
+
+            let item = &self.items[index];
+            let (value, alignment) = if column.checkbox {
+                let value = if self.checked.contains(&index) {
+                    "[x]"
+                } else {
+                    "[ ]"
+                };
+                (value.to_string(), column.alignment)
+            } else {
+                (
+                    column.text(item),
+                    column.row_alignment(|| column.cell(item)),
+                )
+            };
+            if column.column != self.columns[0].column {
+                return column.draw_row(printer, value.as_str(), alignment);
+            }
+
+            let mut prefix = String::new();
+            if self.detail_view.is_some() {
+                prefix.push_str(if self.details.contains_key(&index) {
+                    "▾ "
+                } else {
+                    "▸ "
+                });
+            }
+            if let Some(row) = tree_row {
+                let marker = if !row.has_children {
+                    " "
+                } else if self.expanded.contains(&index) {
+                    "▾"
+                } else {
+                    "▸"
+                };
+                prefix.push_str(&format!("{}{} ", "  ".repeat(row.depth), marker));
+            }
+            column.draw_row(printer, format!("{}{}", prefix, value).as_str(), alignment);
+        });
+    }
+
+    fn draw_group(&self, printer: &Printer, group: usize) {
+        let group = &self.groups[group];
+        let marker = if self.collapsed_groups.contains(&group.key) {
+            "▸"
+        } else {
+            "▾"
+        };
+
+        printer.print_hline((0, 0), printer.size.x, " ");
+        printer.print(
+            (0, 0),
+            format!("{} {} ({})", marker, group.key, group.count).as_str(),
+        );
+    }
+
+    fn on_focus_change(&self, event: &Event) -> EventResult {
+        EventResult::Consumed(None).and(self.emit(TableEventKind::Select, event))
+    }
+
+    fn emit(&self, kind: TableEventKind, event: &Event) -> EventResult {
+        let position = match *event {
+            Event::Mouse {
+                position, offset, ..
+            } => position.checked_sub(offset),
+            _ => None,
+        };
+        let column = match (position, kind) {
+            (Some(position), _) => self.column_for_x(position.x),
+            (None, TableEventKind::Sort(_))
+            | (None, TableEventKind::ColumnSelect)
+            | (None, TableEventKind::HeaderClick) => Some(self.active_column()),
+            (None, _) => None,
+        };
+        let column = match kind {
+            TableEventKind::ContextMenu => column.or_else(|| Some(self.active_column())),
+            _ => column,
+        }
+        .filter(|column| *column < self.columns.len());
+        let screen = self.screen_position(event, column.unwrap_or(0));
+
+        let event = TableEvent {
+            kind,
+            row: self.focused_row(),
+            item: self.focused_item(),
+            column: column.map(|column| self.columns[column].column.clone()),
+            position,
+            trigger: TableTrigger::from(event),
+        };
+
+        let (row, item) = (event.row, event.item);
+        let result = match (kind, row.zip(item)) {
+            (TableEventKind::Select, Some((row, item))) => self
+                .on_select
+                .clone()
+                .map(|cb| EventResult::with_cb(move |s| cb(s, row, item))),
+            (TableEventKind::Submit, Some((row, item))) => self
+                .on_submit
+                .clone()
+                .map(|cb| EventResult::with_cb(move |s| cb(s, row, item))),
+            (TableEventKind::ContextMenu, Some((row, item))) => self
+                .on_context_menu
+                .clone()
+                .zip(event.column.clone())
+                .map(|(cb, column)| {
+                    EventResult::with_cb(move |s| cb(s, row, item, column.clone(), screen))
+                }),
+            (TableEventKind::Scroll, _) => {
+                let offset = self.scroll_offset();
+                self.on_scroll
+                    .clone()
+                    .map(|cb| EventResult::with_cb(move |s| cb(s, offset)))
+            }
+            (TableEventKind::Sort(order), _) => self
+                .on_sort
+                .clone()
+                .zip(event.column.clone())
+                .map(|(cb, column)| EventResult::with_cb(move |s| cb(s, column.clone(), order))),
+            _ => None,
+        }
+        .unwrap_or(EventResult::Ignored);
+
+        match self.on_event.clone() {
+            Some(cb) => result.and(EventResult::with_cb(move |s| cb(s, event.clone()))),
+            None => result,
+        }
+    }
+
+    fn screen_position(&self, event: &Event, column: usize) -> Vec2 {
+        match *event {
+            Event::Mouse {
+                position, offset, ..
+            } => {
+                // Positions of mouse events include the scroll offset
+                let row = position.y.saturating_sub(offset.y);
+                let top = self.scroll_core.content_viewport().top();
+                (position + (0, self.row_y(row) - row)).saturating_sub((0, top))
+            }
+            _ => {
+                let x = self.columns[..column]
+                    .iter()
+                    .map(|column| column.width + 3)
+                    .sum::<usize>();
+                let top = self.scroll_core.content_viewport().top();
+                self.last_offset.get() + (x, 2 + self.row_y(self.focus).saturating_sub(top))
+            }
+        }
+    }
+
+    fn page_height(&self) -> usize {
+        cmp::max(self.scroll_core.last_available_size().y, 1)
+    }
+
+    fn wraps(&self) -> bool {
+        self.wrap_around && self.rows_to_items.len() > 1
+    }
+
+    fn focus_up(&mut self, n: usize) {
+        self.focus -= cmp::min(self.focus, n);
+    }
+
+    fn focus_down(&mut self, n: usize) {
+        self.focus = cmp::min(self.focus + n, self.rows_to_items.len().saturating_sub(1));
+    }
+
+    fn active_column(&self) -> usize {
+        self.columns.iter().position(|c| c.selected).unwrap_or(0)
+    }
+
+    fn column_cancel(&mut self) {
+        self.column_select = false;
+        for column in &mut self.columns {
+            column.selected = column.order != Ordering::Equal;
+        }
+    }
+
+    fn column_next(&mut self) -> bool {
+        let column = self.active_column();
+        if 1 + column < self.columns.len() {
+            self.columns[column].selected = false;
+            self.columns[column + 1].selected = true;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn column_prev(&mut self) -> bool {
+        let column = self.active_column();
+        if column > 0 {
+            self.columns[column].selected = false;
+            self.columns[column - 1].selected = true;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn column_select(&mut self, event: &Event) -> EventResult {
+        let next = self.active_column();
+        let column = self.columns[next].column.clone();
+        let current = self
+            .columns
+            .iter()
+            .position(|c| c.order != Ordering::Equal)
+            .unwrap_or(0);
+
+        let order = if current != next {
+            self.columns[next].default_order
+        } else if self.columns[current].order == Ordering::Less {
+            Ordering::Greater
+        } else {
+            Ordering::Less
+        };
+
+        self.sort_by(column, order);
+
+        let order = self.columns[self.active_column()].order;
+        EventResult::Consumed(None).and(self.emit(TableEventKind::Sort(order), event))
+    }
+
+    fn column_for_x(&self, mut x: usize) -> Option<usize> {
+        for (i, col) in self.columns.iter().enumerate() {
+            if x < col.width {
+                return Some(i);
+            }
+            x = x.saturating_sub(col.width + 1);
+        }
+
+        None
+    }
+
+    fn draw_content(&self, printer: &Printer) {
+        let mut y = 0;
+        for i in 0..self.rows_to_items.len() {
+            let printer = printer.offset((0, y));
+            y += 1;
+            let color = if i == self.focus && self.enabled {
+                if !self.column_select && self.enabled && printer.focused {
+                    theme::ColorStyle::highlight()
+                } else {
+                    theme::ColorStyle::highlight_inactive()
+                }
+            } else if let Some(color) = self.change_color(&printer, self.rows_to_items[i]) {
+                color
+            } else {
+                theme::ColorStyle::primary()
+            };
+
+            match self.group_at(i) {
+                Some(group) => {
+                    let color = if i == self.focus && self.enabled {
+                        color
+                    } else {
+                        theme::ColorStyle::title_primary()
+                    };
+                    printer.with_color(color, |printer| {
+                        self.draw_group(printer, group);
+                    });
+                }
+                None => printer.with_color(color, |printer| {
+                    self.draw_item(printer, i);
+                }),
+            }
+
+            if let Some(detail) = self.details.get(&self.rows_to_items[i]) {
+                detail.view.draw(
+                    &printer
+                        .offset((0, 1))
+                        .cropped((printer.size.x, detail.height))
+                        .focused(false),
+                );
+                y += detail.height;
+            }
+        }
+    }
+
+    fn layout_content(&mut self, size: Vec2) {
         let column_count = self.columns.len();
 
-        // Split up all columns into sized / unsized groups
-        let (mut sized, mut usized): (Vec<&mut TableColumn<H>>, Vec<&mut TableColumn<H>>) = self
-            .columns
-            .iter_mut()
-            .partition(|c| c.requested_width.is_some());
+        // Split up all columns into sized / unsized groups
+        let (mut sized, mut usized): (Vec<_>, Vec<_>) = self
+            .columns
+            .iter_mut()
+            .partition(|c| c.requested_width.is_some());
+
+        // Subtract one for the seperators between our columns (that's column_count - 1)
+        let available_width = size.x.saturating_sub(column_count.saturating_sub(1) * 3);
+
+        // Calculate widths for all requested columns
+        let mut remaining_width = available_width;
+        for column in &mut sized {
+            column.width = match *column.requested_width.as_ref().unwrap() {
+                TableColumnWidth::Percent(width) => cmp::min(
+                    (size.x as f32 / 100.0 * width as f32).ceil() as usize,
+                    remaining_width,
+                ),
+                TableColumnWidth::Absolute(width) => width,
+            };
+            remaining_width = remaining_width.saturating_sub(column.width);
+        }
+
+        // Spread the remaining with across the unsized columns
+        let remaining_columns = usized.len();
+        for column in &mut usized {
+            column.width = (remaining_width as f32 / remaining_columns as f32).floor() as usize;
+        }
+
+        for detail in self.details.values_mut() {
+            detail.view.layout(Vec2::new(size.x, detail.height));
+        }
+
+        self.needs_relayout = false;
+    }
+
+    fn content_required_size(&mut self, req: Vec2) -> Vec2 {
+        let height = self.page_height();
+        for detail in self.details.values_mut() {
+            detail.height = detail.view.required_size(Vec2::new(req.x, height)).y;
+        }
+        Vec2::new(req.x, self.row_y(self.rows_to_items.len()))
+    }
+
+    fn on_inner_event(&mut self, event: Event) -> EventResult {
+        // Detail rows take up additional lines, so mouse positions are
+        // translated into rows before handling them
+        let event = match event {
+            Event::Mouse {
+                position,
+                offset,
+                event: mouse_event,
+            } if !self.details.is_empty() => {
+                match position
+                    .checked_sub(offset)
+                    .and_then(|p| self.row_at_y(p.y))
+                {
+                    Some((row, Some(_))) => return self.on_detail_event(row, event),
+                    Some((row, None)) => Event::Mouse {
+                        position: Vec2::new(position.x, offset.y + row),
+                        offset,
+                        event: mouse_event,
+                    },
+                    None => event,
+                }
+            }
+            event => event,
+        };
+
+        // Keys are offered to the embedded view of the active cell first
+        if let (Some(column), false) = (self.active_cell, self.column_select) {
+            if !matches!(event, Event::Mouse { .. }) {
+                let result = self.on_cell_event(self.focus, column, event.clone());
+                if result.is_consumed() {
+                    return result;
+                }
+            }
+        }
+
+        let last_focus = self.focus;
+        let mut emitted = Vec::new();
+        let mut cell_result = EventResult::Ignored;
+        match (self.keymap.action(&event), event.clone()) {
+            (Some(TableAction::GroupBy), _) if self.column_select => {
+                let column = self.columns[self.active_column()].column.clone();
+                if self.group_by.as_ref() == Some(&column) {
+                    self.set_group_by(None);
+                } else {
+                    self.set_group_by(Some(column));
+                }
+                self.column_cancel();
+                return EventResult::Consumed(None);
+            }
+            (Some(TableAction::ColumnNext), _)
+                if !self.column_select
+                    && self
+                        .group()
+                        .map_or(false, |key| self.collapsed_groups.contains(key)) =>
+            {
+                let key = self.group().unwrap().to_string();
+                self.expand_group(&key);
+                return EventResult::Consumed(None);
+            }
+            (Some(TableAction::ColumnPrev), _)
+                if !self.column_select
+                    && self
+                        .group()
+                        .map_or(false, |key| !self.collapsed_groups.contains(key)) =>
+            {
+                let key = self.group().unwrap().to_string();
+                self.collapse_group(&key);
+                return EventResult::Consumed(None);
+            }
+            (Some(TableAction::Submit), _) if !self.column_select && self.group().is_some() => {
+                return self.on_submit_event(&event);
+            }
+            (Some(TableAction::ColumnNext), _)
+                if !self.column_select
+                    && self.focused_tree_row().map_or(false, |(index, row)| {
+                        row.has_children && !self.expanded.contains(&index)
+                    }) =>
+            {
+                let index = self.current_item().unwrap();
+                self.expand_item(ItemIndex(index));
+                return EventResult::Consumed(None);
+            }
+            (Some(TableAction::ColumnPrev), _)
+                if !self.column_select
+                    && self.focused_tree_row().map_or(false, |(index, row)| {
+                        self.expanded.contains(&index) || row.parent.is_some()
+                    }) =>
+            {
+                let (index, row) = self.focused_tree_row().unwrap();
+                if self.expanded.contains(&index) {
+                    self.collapse_item(ItemIndex(index));
+                    return EventResult::Consumed(None);
+                } else if let Some(row) = row.parent.and_then(|parent| self.row_of_item(parent)) {
+                    self.focus = row;
+                }
+            }
+            (Some(TableAction::Submit), _)
+                if !self.column_select
+                    && self.on_submit.is_none()
+                    && self
+                        .focused_tree_row()
+                        .map_or(false, |(_, row)| row.has_children) =>
+            {
+                let index = self.current_item().unwrap();
+                self.toggle_item(ItemIndex(index));
+                return EventResult::Consumed(None);
+            }
+            (Some(action @ TableAction::CellNext), _)
+            | (Some(action @ TableAction::CellPrev), _)
+                if !self.column_select && self.has_cell_views() =>
+            {
+                let mut columns =
+                    (0..self.columns.len()).filter(|&i| self.columns[i].view.is_some());
+                self.active_cell = match (action, self.active_cell) {
+                    (TableAction::CellNext, None) => columns.next(),
+                    (TableAction::CellNext, Some(active)) => columns.find(|&i| i > active),
+                    (_, None) => columns.next_back(),
+                    (_, Some(active)) => columns.rev().find(|&i| i < active),
+                };
+                return match self.active_cell {
+                    Some(_) => EventResult::Consumed(None),
+                    None => EventResult::Ignored,
+                };
+            }
+            (
+                _,
+                Event::Mouse {
+                    position,
+                    offset,
+                    event: mouse_event,
+                },
+            ) if !matches!(mouse_event, MouseEvent::WheelUp | MouseEvent::WheelDown)
+                && position
+                    .checked_sub(offset)
+                    .and_then(|p| self.cell_view_at(p))
+                    .is_some() =>
+            {
+                let row = position.y - offset.y;
+                let column = self.cell_view_at(position - offset).unwrap();
+                if let MouseEvent::Press(_) = mouse_event {
+                    self.column_cancel();
+                    self.select_row(row);
+                    self.active_cell = Some(column);
+                }
+                cell_result = self.on_cell_event(row, column, event.clone());
+            }
+            (
+                _,
+                Event::Mouse {
+                    position,
+                    offset,
+                    event: MouseEvent::Press(MouseButton::Left),
+                },
+            ) if position
+                .checked_sub(offset)
+                .and_then(|p| self.detail_marker_at(p))
+                .is_some() =>
+            {
+                let index = self
+                    .detail_marker_at(position.checked_sub(offset).unwrap())
+                    .unwrap();
+                self.column_cancel();
+                self.select_row(position.y - offset.y);
+                self.toggle_detail(ItemIndex(index));
+                cell_result = EventResult::Consumed(None);
+            }
+            (
+                _,
+                Event::Mouse {
+                    position,
+                    offset,
+                    event: MouseEvent::Press(MouseButton::Left),
+                },
+            ) if position
+                .checked_sub(offset)
+                .and_then(|p| self.tree_marker_at(p))
+                .is_some() =>
+            {
+                let index = self
+                    .tree_marker_at(position.checked_sub(offset).unwrap())
+                    .unwrap();
+                self.column_cancel();
+                self.toggle_item(ItemIndex(index));
+                return EventResult::Consumed(None);
+            }
+            (
+                _,
+                Event::Mouse {
+                    position,
+                    offset,
+                    event: MouseEvent::Press(MouseButton::Left),
+                },
+            ) if position
+                .checked_sub(offset)
+                .and_then(|p| self.checkbox_at(p))
+                .is_some() =>
+            {
+                let index = self.checkbox_at(position - offset).unwrap();
+                let checked = !self.checked.contains(&index);
+                self.column_cancel();
+                self.select_row(position.y - offset.y);
+                cell_result = self.check_items(vec![index], checked);
+            }
+            (Some(TableAction::ColumnNext), _) => {
+                if self.column_select {
+                    if !self.column_next() {
+                        return EventResult::Ignored;
+                    }
+                } else {
+                    self.column_select = true;
+                }
+                emitted.push(TableEventKind::ColumnSelect);
+            }
+            (Some(TableAction::ColumnPrev), _) => {
+                if self.column_select {
+                    if !self.column_prev() {
+                        return EventResult::Ignored;
+                    }
+                } else {
+                    self.column_select = true;
+                }
+                emitted.push(TableEventKind::ColumnSelect);
+            }
+            (Some(TableAction::FocusUp), _)
+                if self.focus > 0 || self.column_select || self.wraps() =>
+            {
+                if self.column_select {
+                    self.column_cancel();
+                } else if self.focus == 0 {
+                    self.focus = self.rows_to_items.len() - 1;
+                } else {
+                    self.focus_up(1);
+                }
+            }
+            (Some(TableAction::FocusDown), _)
+                if self.focus + 1 < self.rows_to_items.len()
+                    || self.column_select
+                    || self.wraps() =>
+            {
+                if self.column_select {
+                    self.column_cancel();
+                } else if self.focus + 1 == self.rows_to_items.len() {
+                    self.focus = 0;
+                } else {
+                    self.focus_down(1);
+                }
+            }
+            (Some(TableAction::PageUp), _) => {
+                self.column_cancel();
+                self.focus_up(self.page_height());
+            }
+            (Some(TableAction::PageDown), _) => {
+                self.column_cancel();
+                self.focus_down(self.page_height());
+            }
+            (Some(TableAction::HalfPageUp), _) => {
+                self.column_cancel();
+                self.focus_up(cmp::max(self.page_height() / 2, 1));
+            }
+            (Some(TableAction::HalfPageDown), _) => {
+                self.column_cancel();
+                self.focus_down(cmp::max(self.page_height() / 2, 1));
+            }
+            (Some(TableAction::FocusFirst), _) => {
+                self.column_cancel();
+                self.focus = 0;
+            }
+            (Some(TableAction::FocusLast), _) => {
+                self.column_cancel();
+                self.focus = self.rows_to_items.len().saturating_sub(1);
+            }
+            (Some(TableAction::Submit), _) => {
+                if self.column_select {
+                    return self.column_select(&event);
+                } else if self.is_empty() {
+                    return self.emit(TableEventKind::SubmitEmpty, &event);
+                } else if self.on_submit.is_some() || self.on_event.is_some() {
+                    return self.on_submit_event(&event);
+                }
+            }
+            (
+                _,
+                Event::Mouse {
+                    position,
+                    offset,
+                    event: MouseEvent::Press(MouseButton::Left),
+                },
+            ) if !self.is_empty() => match position.checked_sub(offset) {
+                Some(position) if position.y < self.rows_to_items.len() => {
+                    let now = Instant::now();
+                    let double = self.last_click.take().map_or(false, |(time, row)| {
+                        row == position.y && now.duration_since(time) <= self.double_click_interval
+                    });
+                    if !double {
+                        self.last_click = Some((now, position.y));
+                    }
+
+                    let submit = match self.submit_mode {
+                        SubmitMode::ClickFocused => position.y == self.focus,
+                        SubmitMode::DoubleClick => double,
+                        SubmitMode::Never => false,
+                    };
+
+                    self.column_cancel();
+                    self.focus = position.y;
+                    if double {
+                        emitted.push(TableEventKind::DoubleClick);
+                    }
+                    if submit {
+                        return emitted
+                            .into_iter()
+                            .fold(self.on_submit_event(&event), |result, kind| {
+                                result.and(self.emit(kind, &event))
+                            });
+                    }
+                }
+                _ => return EventResult::Ignored,
+            },
+            (
+                _,
+                Event::Mouse {
+                    position,
+                    offset,
+                    event: MouseEvent::Press(MouseButton::Right),
+                },
+            ) => match position.checked_sub(offset) {
+                Some(position) => {
+                    emitted.push(TableEventKind::RightClick);
+                    if position.y < self.rows_to_items.len() {
+                        self.column_cancel();
+                        self.focus = position.y;
+                        if self.current_item().is_some() {
+                            emitted.push(TableEventKind::ContextMenu);
+                        }
+                    }
+                }
+                None => return EventResult::Ignored,
+            },
+            (Some(TableAction::ContextMenu), _)
+                if !self.column_select && self.current_item().is_some() =>
+            {
+                return self.emit(TableEventKind::ContextMenu, &event);
+            }
+            (Some(TableAction::Sort), _) if !self.columns.is_empty() => {
+                return self.column_select(&event);
+            }
+            (Some(action @ TableAction::ToggleSelect), _)
+                if !self.column_select
+                    && self.has_checkboxes()
+                    && self.current_item().is_some() =>
+            {
+                let index = self.current_item().unwrap();
+                let checked = !self.checked.contains(&index);
+                return self
+                    .check_items(vec![index], checked)
+                    .and(self.emit(TableEventKind::Action(action), &event));
+            }
+            (Some(action @ TableAction::ToggleSelect), _)
+            | (Some(action @ TableAction::ToggleDetail), _)
+                if !self.column_select
+                    && self.detail_view.is_some()
+                    && self.current_item().is_some() =>
+            {
+                let index = self.current_item().unwrap();
+                self.toggle_detail(ItemIndex(index));
+                return match action {
+                    TableAction::ToggleSelect => self.emit(TableEventKind::Action(action), &event),
+                    _ => EventResult::Consumed(None),
+                };
+            }
+            (Some(action @ TableAction::ToggleSelect), _)
+            | (Some(action @ TableAction::Filter), _) => {
+                return self.emit(TableEventKind::Action(action), &event);
+            }
+            (
+                _,
+                Event::Mouse {
+                    position,
+                    offset,
+                    event: MouseEvent::Press(button),
+                },
+            ) if !self.is_empty() => match position.checked_sub(offset) {
+                Some(position) if position.y < self.rows_to_items.len() => {
+                    self.column_cancel();
+                    self.focus = position.y;
+                    if button == MouseButton::Middle {
+                        emitted.push(TableEventKind::MiddleClick);
+                    }
+                }
+                _ => return EventResult::Ignored,
+            },
+            (
+                _,
+                Event::Mouse {
+                    position,
+                    offset,
+                    event: mouse_event @ MouseEvent::Release(_),
+                },
+            )
+            | (
+                _,
+                Event::Mouse {
+                    position,
+                    offset,
+                    event: mouse_event @ MouseEvent::Hold(_),
+                },
+            ) => match position.checked_sub(offset) {
+                Some(position) if position.y < self.rows_to_items.len() => {
+                    let kind = match mouse_event {
+                        MouseEvent::Release(_) => TableEventKind::Release,
+                        _ => TableEventKind::Hold,
+                    };
+                    return self.emit(kind, &event);
+                }
+                _ => return EventResult::Ignored,
+            },
+            (_, Event::Mouse { .. }) => return EventResult::Ignored,
+            _ => return self.on_detail_event(self.focus, event),
+        }
+
+        let focus = self.focus;
+        self.update_following();
+
+        let result = if self.column_select {
+            EventResult::Consumed(None)
+        } else if !self.is_empty() && last_focus != focus {
+            self.on_focus_change(&event)
+        } else {
+            EventResult::Ignored
+        };
+        let result = cell_result.and(result);
+
+        emitted
+            .into_iter()
+            .fold(result, |result, kind| result.and(self.emit(kind, &event)))
+    }
+
+    fn inner_important_area(&self, size: Vec2) -> Rect {
+        let height = self.scroll_core.last_available_size().y;
+        let margin = cmp::min(self.scrolloff, height.saturating_sub(1) / 2);
+        let last = self.rows_to_items.len().saturating_sub(1);
+        let top = self.row_y(self.focus.saturating_sub(margin));
+        let bottom = cmp::max(
+            self.row_y(cmp::min(self.focus + margin, last)),
+            self.row_y(self.focus) + self.detail_height(self.focus),
+        );
+        let bottom = cmp::min(bottom, size.y.saturating_sub(1));
+        Rect::from_corners((0, top), (size.x.saturating_sub(1), cmp::max(top, bottom)))
+    }
+
+    fn on_submit_event(&mut self, event: &Event) -> EventResult {
+        if let Some(key) = self.group().map(str::to_string) {
+            self.toggle_group(&key);
+            return EventResult::Consumed(None);
+        }
+        self.emit(TableEventKind::Submit, event)
+    }
+}
+
+impl<T: TableViewItem<H> + 'static, H: Eq + Hash + Clone + MaybeSync + 'static> View
+    for TableView<T, H>
+{
+    fn draw(&self, printer: &Printer) {
+        self.last_offset.set(printer.offset);
+        let checkbox = if self.has_checkboxes() {
+            let items = self.row_items();
+            match items
+                .iter()
+                .filter(|item| self.checked.contains(item))
+                .count()
+            {
+                0 => "[ ]",
+                count if count == items.len() => "[x]",
+                _ => "[-]",
+            }
+        } else {
+            ""
+        };
+        self.draw_columns(printer, "╷ ", |printer, column| {
+            let color = if self.enabled && (column.order != Ordering::Equal || column.selected) {
+                if self.column_select && column.selected && self.enabled && printer.focused {
+                    theme::ColorStyle::highlight()
+                } else {
+                    theme::ColorStyle::highlight_inactive()
+                }
+            } else {
+                theme::ColorStyle::primary()
+            };
+
+            printer.with_color(color, |printer| {
+                if column.checkbox {
+                    column.draw_checkbox_header(printer, checkbox);
+                } else {
+                    column.draw_header(printer);
+                }
+            });
+        });
+
+        self.draw_columns(
+            &printer.offset((0, 1)).focused(true),
+            "┴─",
+            |printer, column| {
+                printer.print_hline((0, 0), column.width + 1, "─");
+            },
+        );
+
+        let footer_height = if self.has_footer() { 2 } else { 0 };
+        let body_height = printer.size.y.saturating_sub(2 + footer_height);
+
+        // Extend the vertical bars to the end of the table body
+        for y in 2..2 + body_height {
+            self.draw_columns(&printer.offset((0, y)), "┆ ", |_, _| ());
+        }
+
+        if footer_height > 0 {
+            let y = 2 + body_height;
+            self.draw_columns(
+                &printer.offset((0, y)).focused(true),
+                "┼─",
+                |printer, column| {
+                    printer.print_hline((0, 0), column.width + 1, "─");
+                },
+            );
+            self.draw_columns(&printer.offset((0, y + 1)), "╵ ", |printer, column| {
+                let footer = column.footer.as_str();
+                let alignment =
+                    column.row_alignment(|| CellValue::from(footer.parse::<f64>().ok()));
+                printer.with_color(theme::ColorStyle::title_primary(), |printer| {
+                    column.draw_row(printer, footer, alignment);
+                });
+            });
+        }
+
+        let printer = &printer
+            .offset((0, 2))
+            .cropped((printer.size.x, body_height))
+            .focused(true);
+        scroll::draw(self, printer, Self::draw_content);
+    }
+
+    fn layout(&mut self, size: Vec2) {
+        if self.needs_relayout {
+            self.update_footer();
+        }
+
+        let footer_height = if self.has_footer() { 2 } else { 0 };
+        scroll::layout(
+            self,
+            size.saturating_sub((0, 2 + footer_height)),
+            self.needs_relayout,
+            Self::layout_content,
+            Self::content_required_size,
+        );
+        self.update_cell_views();
+    }
+
+    fn take_focus(&mut self, _: Direction) -> bool {
+        self.enabled
+    }
+
+    fn call_on_any<'a>(&mut self, selector: &Selector<'_>, callback: AnyCb<'a>) {
+        for detail in self.details.values_mut() {
+            detail.view.call_on_any(selector, &mut *callback);
+        }
+        for view in self.cell_views.values_mut() {
+            view.call_on_any(selector, &mut *callback);
+        }
+    }
+
+    fn on_event(&mut self, event: Event) -> EventResult {
+        if !self.enabled {
+            return EventResult::Ignored;
+        }
+
+        if let Event::Mouse {
+            event: MouseEvent::WheelUp,
+            ..
+        } = event
+        {
+            self.following = false;
+        }
+
+        let scroll_offset = self.scroll_offset();
+        let trigger = event.clone();
+        let result = match event {
+            Event::Mouse {
+                position,
+                offset,
+                event: MouseEvent::Press(MouseButton::Left),
+            } if position.checked_sub(offset).map_or(false, |p| p.y == 0) => {
+                if let Some(position) = position.checked_sub(offset) {
+                    if let Some(col) = self.column_for_x(position.x) {
+                        let header = self.emit(TableEventKind::HeaderClick, &event);
+                        if self.columns[col].checkbox {
+                            let items = self.row_items();
+                            let checked = items.iter().any(|item| !self.checked.contains(item));
+                            return header.and(self.check_items(items, checked));
+                        } else if self.column_select && self.columns[col].selected {
+                            return header.and(self.column_select(&event));
+                        } else {
+                            let active = self.active_column();
+                            self.columns[active].selected = false;
+                            self.columns[col].selected = true;
+                            self.column_select = true;
+                            return header.and(self.emit(TableEventKind::ColumnSelect, &event));
+                        }
+                    }
+                }
+                EventResult::Ignored
+            }
+            event => scroll::on_event(
+                self,
+                event.relativized((0, 2)),
+                Self::on_inner_event,
+                Self::inner_important_area,
+            ),
+        };
+
+        if self.scroll_offset() != scroll_offset {
+            result.and(self.emit(TableEventKind::Scroll, &trigger))
+        } else {
+            result
+        }
+    }
+
+    fn important_area(&self, size: Vec2) -> Rect {
+        self.inner_important_area(size.saturating_sub((0, 2))) + (0, 2)
+    }
+}
+
+/// Formats an aggregated number, omitting the fraction for whole numbers.
+fn format_number(value: f64) -> String {
+    if value.fract() == 0.0 && value.abs() < 1e15 {
+        format!("{}", value as i64)
+    } else {
+        format!("{:.2}", value)
+    }
+}
+
+/// Returns the new position of `index` after the `removed` range of items has
+/// been removed, or `None` if it was part of the range.
+fn shift_index(index: usize, removed: &Range<usize>) -> Option<usize> {
+    if index < removed.start {
+        Some(index)
+    } else if index >= removed.end {
+        Some(index - (removed.end - removed.start))
+    } else {
+        None
+    }
+}
+
+/// Hashes the key of a keyed item for the key index.
+fn hash_item<T, H>(item: &T) -> u64
+where
+    T: KeyedTableViewItem<H>,
+    H: Eq + Hash + Clone + MaybeSync + 'static,
+{
+    hash_key(&item.key())
+}
+
+fn hash_key<K: Hash>(key: &K) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    key.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Approximates the RGB value of a terminal color.
+fn rgb(color: theme::Color) -> Option<(u8, u8, u8)> {
+    let base = |color: theme::BaseColor, light: bool| {
+        let (high, low, white) = if light {
+            (255, 128, 255)
+        } else {
+            (128, 0, 192)
+        };
+        match color {
+            theme::BaseColor::Black => (low, low, low),
+            theme::BaseColor::Red => (high, 0, 0),
+            theme::BaseColor::Green => (0, high, 0),
+            theme::BaseColor::Yellow => (high, high, 0),
+            theme::BaseColor::Blue => (0, 0, high),
+            theme::BaseColor::Magenta => (high, 0, high),
+            theme::BaseColor::Cyan => (0, high, high),
+            theme::BaseColor::White => (white, white, white),
+        }
+    };
+
+    match color {
+        theme::Color::TerminalDefault => None,
+        theme::Color::Dark(color) => Some(base(color, false)),
+        theme::Color::Light(color) => Some(base(color, true)),
+        theme::Color::Rgb(r, g, b) => Some((r, g, b)),
+        theme::Color::RgbLowRes(r, g, b) => Some((r * 51, g * 51, b * 51)),
+    }
+}
+
+/// A type used for the construction of columns in a
+/// [`TableView`](struct.TableView.html).
+///
+/// Columns may provide their own `display` and `compare` closures, in which
+/// case the item's `to_column` and `cmp` are not used for them. Items which
+/// are only shown via such columns still implement `TableViewItem`, but
+/// `to_column` can return an empty string. Since the column type is local,
+/// this also works for foreign item types:
+///
+/// ```rust
+/// # use cursive_table_view::{TableView, TableViewItem};
+/// #[derive(Copy, Clone, PartialEq, Eq, Hash)]
+/// enum Column {
+///     Name,
+///     Size,
+/// }
+///
+/// impl TableViewItem<Column> for (String, u64) {
+///     fn to_column(&self, _: Column) -> String {
+///         String::new()
+///     }
+/// }
+///
+/// let table = TableView::<(String, u64), Column>::new()
+///     .column(Column::Name, "Name", |c| {
+///         c.display(|item: &(String, u64)| item.0.clone())
+///             .compare(|a: &(String, u64), b: &(String, u64)| a.0.cmp(&b.0))
+///     })
+///     .column(Column::Size, "Size", |c| {
+///         c.display(|item: &(String, u64)| item.1.to_string())
+///             .compare(|a: &(String, u64), b: &(String, u64)| a.1.cmp(&b.1))
+///     })
+///     .items(vec![("a.txt".to_string(), 2048)]);
+/// ```
+pub struct TableColumn<T, H> {
+    column: H,
+    title: String,
+    selected: bool,
+    alignment: HAlign,
+    aligned: bool,
+    order: Ordering,
+    width: usize,
+    default_order: Ordering,
+    requested_width: Option<TableColumnWidth>,
+    aggregate: Option<TableAggregate<T>>,
+    footer: String,
+    display: Option<DisplayCallback<T>>,
+    compare: Option<Rc<CompareFn<T>>>,
+    view: Option<ItemViewCallback<T>>,
+    checkbox: bool,
+}
+
+/// Callback displaying the value of an item for a single column.
+///
+/// This is a private type to help readability.
+type DisplayCallback<T> = Rc<dyn Fn(&T) -> String>;
+
+/// Function comparing two items for a single column.
+///
+/// This is a private type to help readability.
+#[cfg(feature = "rayon")]
+type CompareFn<T> = dyn Fn(&T, &T) -> Ordering + Sync;
+
+/// Function comparing two items for a single column.
+///
+/// This is a private type to help readability.
+#[cfg(not(feature = "rayon"))]
+type CompareFn<T> = dyn Fn(&T, &T) -> Ordering;
+
+enum TableColumnWidth {
+    Percent(usize),
+    Absolute(usize),
+}
+
+impl<T, H: Clone + 'static> TableColumn<T, H> {
+    /// Sets the default ordering of the column.
+    pub fn ordering(mut self, order: Ordering) -> Self {
+        self.default_order = order;
+        self
+    }
+
+    /// Sets the horizontal text alignment of the column.
+    ///
+    /// Without an explicit alignment, numeric cell values are aligned to the
+    /// right and all other values to the left.
+    pub fn align(mut self, alignment: HAlign) -> Self {
+        self.alignment = alignment;
+        self.aligned = true;
+        self
+    }
+
+    /// Sets how many characters of width this column will try to occupy.
+    pub fn width(mut self, width: usize) -> Self {
+        self.requested_width = Some(TableColumnWidth::Absolute(width));
+        self
+    }
+
+    /// Sets what percentage of the width of the entire table this column will
+    /// try to occupy.
+    pub fn width_percent(mut self, width: usize) -> Self {
+        self.requested_width = Some(TableColumnWidth::Percent(width));
+        self
+    }
+
+    /// Sets the summary shown for this column in the footer of the table.
+    pub fn aggregate(mut self, aggregate: TableAggregate<T>) -> Self {
+        self.aggregate = Some(aggregate);
+        self
+    }
+
+    /// Sets a closure used to display the items of this column instead of
+    /// `TableViewItem::to_column`.
+    ///
+    /// This allows for computed columns which are not known to the item
+    /// type itself.
+    pub fn display<F>(mut self, display: F) -> Self
+    where
+        F: Fn(&T) -> String + 'static,
+    {
+        self.display = Some(Rc::new(display));
+        self
+    }
+
+    /// Sets a closure used to sort the items of this column instead of
+    /// `TableViewItem::cmp`.
+    ///
+    /// With the `rayon` feature enabled the closure must be `Sync`.
+    pub fn compare<F>(mut self, compare: F) -> Self
+    where
+        F: Fn(&T, &T) -> Ordering + MaybeSync + 'static,
+    {
+        self.compare = Some(Rc::new(compare));
+        self
+    }
+
+    /// Sets a closure creating a view, such as a `Button`, `Checkbox` or
+    /// `ProgressBar`, which is shown in this column instead of text.
+    ///
+    /// Views are only kept for visible rows and are recreated from the item
+    /// when a row scrolls back into view, so any state should live in the
+    /// items. Clicking a view focuses its row, while keys reach it once its
+    /// cell was activated via `<Tab>`. Callbacks of the view can therefore
+    /// look up the item through the focus of the table.
+    ///
+    /// Sorting and aggregates still use the text of the column.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// table.column(Column::Action, "", |c| {
+    ///     c.width(9).view(|_| Box::new(Button::new("Restart", restart_focused)))
+    /// })
+    /// ```
+    pub fn view<F>(mut self, view: F) -> Self
+    where
+        F: Fn(&T) -> Box<dyn View> + 'static,
+    {
+        self.view = Some(Rc::new(view));
+        self
+    }
+
+    /// Turns this column into a checkbox column, which draws `[x]` for
+    /// checked and `[ ]` for unchecked items instead of text.
+    ///
+    /// Clicking a checkbox or pressing `<Space>` toggles the item, while
+    /// clicking the checkbox in the header toggles all rows. The checked
+    /// items are available via `TableView::checked_items`.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// table.column(Column::Selected, "All", |c| c.checkbox().width(7))
+    /// ```
+    pub fn checkbox(mut self) -> Self {
+        self.checkbox = true;
+        self
+    }
+
+    fn new(column: H, title: String) -> Self {
+        Self {
+            column,
+            title,
+            selected: false,
+            alignment: HAlign::Left,
+            aligned: false,
+            order: Ordering::Equal,
+            width: 0,
+            default_order: Ordering::Less,
+            requested_width: None,
+            aggregate: None,
+            footer: String::new(),
+            display: None,
+            compare: None,
+            view: None,
+            checkbox: false,
+        }
+    }
+
+    fn draw_header(&self, printer: &Printer) {
+        let order = match self.order {
+            Ordering::Less => "^",
+            Ordering::Greater => "v",
+            Ordering::Equal => " ",
+        };
+
+        let header = match self.alignment {
+            HAlign::Left => format!(
+                "{:<width$} [{}]",
+                self.title,
+                order,
+                width = self.width.saturating_sub(4)
+            ),
+            HAlign::Right => format!(
+                "{:>width$} [{}]",
+                self.title,
+                order,
+                width = self.width.saturating_sub(4)
+            ),
+            HAlign::Center => format!(
+                "{:^width$} [{}]",
+                self.title,
+                order,
+                width = self.width.saturating_sub(4)
+            ),
+        };
+
+        printer.print((0, 0), header.as_str());
+    }
+
+    fn draw_checkbox_header(&self, printer: &Printer, checkbox: &str) {
+        let header = format!("{} {}", checkbox, self.title);
+        printer.print(
+            (0, 0),
+            format!("{:<width$}", header, width = self.width).as_str(),
+        );
+    }
+
+    fn row_alignment<F: FnOnce() -> CellValue>(&self, cell: F) -> HAlign {
+        if !self.aligned && cell().is_numeric() {
+            HAlign::Right
+        } else {
+            self.alignment
+        }
+    }
+
+    fn draw_row(&self, printer: &Printer, value: &str, alignment: HAlign) {
+        let value = match alignment {
+            HAlign::Left => format!("{:<width$} ", value, width = self.width),
+            HAlign::Right => format!("{:>width$} ", value, width = self.width),
+            HAlign::Center => format!("{:^width$} ", value, width = self.width),
+        };
+
+        printer.print((0, 0), value.as_str());
+    }
+}
+
+impl<T, H> TableColumn<T, H>
+where
+    T: TableViewItem<H>,
+    H: Eq + Hash + Clone + MaybeSync + 'static,
+{
+    fn cell(&self, item: &T) -> CellValue {
+        match self.display {
+            Some(ref display) => CellValue::Text(display(item)),
+            None => item.cell(self.column.clone()),
+        }
+    }
+
+    fn text(&self, item: &T) -> String {
+        match self.display {
+            Some(ref display) => display(item),
+            None => item.to_column(self.column.clone()),
+        }
+    }
+
+    fn number(&self, item: &T) -> Option<f64> {
+        match self.display {
+            Some(ref display) => display(item).trim().parse().ok(),
+            None => item.to_number(self.column.clone()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cursive::event::Key;
+
+    #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+    enum SimpleColumn {
+        Name,
+    }
+
+    #[allow(dead_code)]
+    impl SimpleColumn {
+        fn as_str(&self) -> &str {
+            match *self {
+                SimpleColumn::Name => "Name",
+            }
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    struct SimpleItem {
+        name: String,
+    }
+
+    impl TableViewItem<SimpleColumn> for SimpleItem {
+        fn to_column(&self, column: SimpleColumn) -> String {
+            match column {
+                SimpleColumn::Name => self.name.to_string(),
+            }
+        }
+
+        fn cmp(&self, other: &Self, column: SimpleColumn) -> Ordering
+        where
+            Self: Sized,
+        {
+            match column {
+                SimpleColumn::Name => self.name.cmp(&other.name),
+            }
+        }
+    }
+
+    impl KeyedTableViewItem<SimpleColumn> for SimpleItem {
+        type Key = String;
+
+        fn key(&self) -> String {
+            self.name.clone()
+        }
+    }
+
+    impl TreeTableItem<SimpleColumn> for SimpleItem {
+        fn parent(&self) -> Option<String> {
+            self.name
+                .rfind('/')
+                .map(|separator| self.name[..separator].to_string())
+        }
+    }
+
+    fn setup_test_table() -> TableView<SimpleItem, SimpleColumn> {
+        TableView::<SimpleItem, SimpleColumn>::new()
+            .column(SimpleColumn::Name, "Name", |c| c.width_percent(20))
+    }
+
+    #[test]
+    fn should_insert_into_existing_table() {
+        let mut simple_table = setup_test_table();
 
-        // Subtract one for the seperators between our columns (that's column_count - 1)
-        let available_width = size.x.saturating_sub(column_count.saturating_sub(1) * 3);
+        let mut simple_items = Vec::new();
 
-        // Calculate widths for all requested columns
-        let mut remaining_width = available_width;
-        for column in &mut sized {
-            column.width = match *column.requested_width.as_ref().unwrap() {
-                TableColumnWidth::Percent(width) => cmp::min(
-                    (size.x as f32 / 100.0 * width as f32).ceil() as usize,
-                    remaining_width,
-                ),
-                TableColumnWidth::Absolute(width) => width,
-            };
-            remaining_width = remaining_width.saturating_sub(column.width);
+        for i in 1..=10 {
+            simple_items.push(SimpleItem {
+                name: format!("{} - Name", i),
+            });
         }
 
-        // Spread the remaining with across the unsized columns
-        let remaining_columns = usized.len();
-        for column in &mut usized {
-            column.width = (remaining_width as f32 / remaining_columns as f32).floor() as usize;
-        }
+        // Insert First Batch of Items
+        simple_table.set_items(simple_items);
 
-        self.needs_relayout = false;
+        // Test for Additional item insertion
+        simple_table.insert_item(SimpleItem {
+            name: format!("{} Name", 11),
+        });
+
+        assert!(simple_table.len() == 11);
     }
 
-    fn content_required_size(&mut self, req: Vec2) -> Vec2 {
-        Vec2::new(req.x, self.rows_to_items.len())
+    #[test]
+    fn should_insert_into_empty_table() {
+        let mut simple_table = setup_test_table();
+
+        // Test for First item insertion
+        simple_table.insert_item(SimpleItem {
+            name: format!("{} Name", 1),
+        });
+
+        assert!(simple_table.len() == 1);
     }
 
-    fn on_inner_event(&mut self, event: Event) -> EventResult {
-        let last_focus = self.focus;
-        match event {
-            Event::Key(Key::Right) => {
-                if self.column_select {
-                    if !self.column_next() {
-                        return EventResult::Ignored;
-                    }
-                } else {
-                    self.column_select = true;
-                }
-            }
-            Event::Key(Key::Left) => {
-                if self.column_select {
-                    if !self.column_prev() {
-                        return EventResult::Ignored;
-                    }
-                } else {
-                    self.column_select = true;
-                }
-            }
-            Event::Key(Key::Up) if self.focus > 0 || self.column_select => {
-                if self.column_select {
-                    self.column_cancel();
-                } else {
-                    self.focus_up(1);
-                }
+    #[test]
+    fn should_keep_focus_on_replace_items() {
+        let mut simple_table = setup_test_table();
+
+        let names = ["b", "d", "c", "a"];
+        simple_table.set_items(
+            names
+                .iter()
+                .map(|name| SimpleItem {
+                    name: name.to_string(),
+                })
+                .collect(),
+        );
+        simple_table.sort_by(SimpleColumn::Name, Ordering::Less);
+        simple_table.focus_item(simple_table.find_by_key(&"c".to_string()).unwrap());
+
+        // Replace the items with a new set missing "a"
+        simple_table.replace_items(
+            ["c", "b", "e", "d"]
+                .iter()
+                .map(|name| SimpleItem {
+                    name: name.to_string(),
+                })
+                .collect(),
+        );
+
+        let index = simple_table.focused_item().unwrap();
+        assert_eq!(simple_table.borrow_item(index).unwrap().name, "c");
+        assert_eq!(simple_table.focused_row(), Some(RowIndex(1)));
+    }
+
+    #[test]
+    fn should_keep_checks_on_replace_items() {
+        let items = |names: &[&str]| {
+            names
+                .iter()
+                .map(|name| SimpleItem {
+                    name: name.to_string(),
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let mut simple_table = TableView::<SimpleItem, SimpleColumn>::new()
+            .column(SimpleColumn::Name, "All", |c| c.checkbox().width(7))
+            .items(items(&["a", "b", "c"]));
+        simple_table.set_checked(ItemIndex(1), true);
+        simple_table.set_checked(ItemIndex(2), true);
+
+        // "c" is gone, then "b" moves to another index
+        simple_table.replace_items(items(&["d", "b", "a"]));
+        assert_eq!(simple_table.checked_items(), vec![ItemIndex(1)]);
+        simple_table.replace_items(items(&["b", "a"]));
+        assert_eq!(simple_table.checked_items(), vec![ItemIndex(0)]);
+    }
+
+    #[test]
+    fn should_keep_details_on_replace_items() {
+        use cursive::views::TextView;
+
+        let items = |names: &[&str]| {
+            names
+                .iter()
+                .map(|name| SimpleItem {
+                    name: name.to_string(),
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let mut simple_table = TableView::<SimpleItem, SimpleColumn>::new()
+            .column(SimpleColumn::Name, "All", |c| c.checkbox().width(7))
+            .detail_view(|item: &SimpleItem| Box::new(TextView::new(item.name.clone())))
+            .items(items(&["a", "b", "c"]));
+        simple_table.set_checked(ItemIndex(0), true);
+        simple_table.expand_detail(ItemIndex(0));
+        simple_table.expand_detail(ItemIndex(2));
+
+        simple_table.replace_items(items(&["b", "a", "d"]));
+        assert_eq!(simple_table.checked_items(), vec![ItemIndex(1)]);
+        assert!(simple_table.is_detail_expanded(ItemIndex(1)));
+        assert!(!simple_table.is_detail_expanded(ItemIndex(0)));
+        assert!(!simple_table.is_detail_expanded(ItemIndex(2)));
+    }
+
+    #[test]
+    fn should_upsert_and_remove_by_key() {
+        let mut simple_table = setup_test_table();
+
+        assert!(simple_table
+            .upsert_item(SimpleItem {
+                name: "a".to_string(),
+            })
+            .is_none());
+        assert!(simple_table
+            .upsert_item(SimpleItem {
+                name: "a".to_string(),
+            })
+            .is_some());
+        assert_eq!(simple_table.len(), 1);
+
+        assert!(simple_table.remove_by_key(&"a".to_string()).is_some());
+        assert!(simple_table.is_empty());
+    }
+
+    #[test]
+    fn should_move_upserted_items_to_their_sorted_row() {
+        struct Score {
+            name: String,
+            score: usize,
+        }
+
+        impl TableViewItem<SimpleColumn> for Score {
+            fn to_column(&self, _: SimpleColumn) -> String {
+                self.name.clone()
             }
-            Event::Key(Key::Down) if self.focus + 1 < self.items.len() || self.column_select => {
-                if self.column_select {
-                    self.column_cancel();
-                } else {
-                    self.focus_down(1);
-                }
+
+            fn cmp(&self, other: &Self, _: SimpleColumn) -> Ordering {
+                self.score.cmp(&other.score)
             }
-            Event::Key(Key::PageUp) => {
-                self.column_cancel();
-                self.focus_up(10);
+        }
+
+        impl KeyedTableViewItem<SimpleColumn> for Score {
+            type Key = String;
+
+            fn key(&self) -> String {
+                self.name.clone()
             }
-            Event::Key(Key::PageDown) => {
-                self.column_cancel();
-                self.focus_down(10);
+        }
+
+        let score = |name: &str, score| Score {
+            name: name.to_string(),
+            score,
+        };
+        let names = |table: &TableView<Score, SimpleColumn>| {
+            table
+                .rows_to_items
+                .iter()
+                .map(|i| table.items[*i].name.clone())
+                .collect::<Vec<_>>()
+        };
+
+        let mut table = TableView::<Score, SimpleColumn>::new()
+            .column(SimpleColumn::Name, "Name", |c| c)
+            .items(vec![
+                score("a", 1),
+                score("b", 2),
+                score("c", 3),
+                score("d", 4),
+            ]);
+        table.sort_by(SimpleColumn::Name, Ordering::Less);
+        table.focus_item(ItemIndex(2));
+
+        assert_eq!(
+            table.upsert_item(score("a", 5)).map(|old| old.score),
+            Some(1)
+        );
+        assert_eq!(names(&table), vec!["b", "c", "d", "a"]);
+        assert_eq!(table.focused_item(), Some(ItemIndex(2)));
+
+        assert!(table.upsert_item(score("e", 0)).is_none());
+        table.upsert_item(score("c", 0));
+        assert_eq!(names(&table), vec!["e", "c", "b", "d", "a"]);
+        assert_eq!(table.focused_item(), Some(ItemIndex(2)));
+
+        table.remove_by_key(&"b".to_string());
+        assert_eq!(table.find_by_key(&"e".to_string()), Some(ItemIndex(3)));
+        assert_eq!(table.find_by_key(&"b".to_string()), None);
+    }
+
+    #[test]
+    fn should_apply_queued_handle_ops_in_order() {
+        use cursive::backend::Backend;
+        use cursive::views::NamedView;
+        use std::thread;
+
+        // Unlike `backend::Dummy`, this does not exit on the first poll
+        struct Idle;
+
+        impl Backend for Idle {
+            fn poll_event(&mut self) -> Option<Event> {
+                None
             }
-            Event::Key(Key::Home) => {
-                self.column_cancel();
-                self.focus = 0;
+            fn refresh(&mut self) {}
+            fn has_colors(&self) -> bool {
+                false
             }
-            Event::Key(Key::End) => {
-                self.column_cancel();
-                self.focus = self.items.len().saturating_sub(1);
+            fn screen_size(&self) -> Vec2 {
+                Vec2::new(40, 10)
             }
-            Event::Key(Key::Enter) => {
-                if self.column_select {
-                    return self.column_select();
-                } else if !self.is_empty() && self.on_submit.is_some() {
-                    return self.on_submit_event();
-                }
+            fn print_at(&self, _: Vec2, _: &str) {}
+            fn clear(&self, _: theme::Color) {}
+            fn set_color(&self, colors: theme::ColorPair) -> theme::ColorPair {
+                colors
             }
-            Event::Mouse {
-                position,
-                offset,
-                event: MouseEvent::Press(MouseButton::Left),
-            } if !self.is_empty()
-                && position
-                    .checked_sub(offset)
-                    .map_or(false, |p| p.y == self.focus) =>
-            {
-                self.column_cancel();
-                return self.on_submit_event();
+            fn set_effect(&self, _: theme::Effect) {}
+            fn unset_effect(&self, _: theme::Effect) {}
+        }
+
+        let mut siv = Cursive::new();
+        siv.add_layer(NamedView::new("table", setup_test_table()));
+
+        let handle = TableHandle::<SimpleItem, SimpleColumn>::new("table", siv.cb_sink().clone());
+        let producer = handle.clone();
+        thread::spawn(move || {
+            for i in 0..5 {
+                producer.push(SimpleItem {
+                    name: format!("{}", i),
+                });
             }
-            Event::Mouse {
-                position,
-                offset,
-                event: MouseEvent::Press(_),
-            } if !self.is_empty() => match position.checked_sub(offset) {
-                Some(position) if position.y < self.rows_to_items.len() => {
-                    self.column_cancel();
-                    self.focus = position.y;
-                }
-                _ => return EventResult::Ignored,
-            },
-            _ => return EventResult::Ignored,
+            producer.update(ItemIndex(1), |item| item.name.push_str(" updated"));
+            producer.remove(ItemIndex(0));
+        })
+        .join()
+        .unwrap();
+
+        // All operations of the burst are applied by a single callback
+        assert_eq!(siv.cb_sink().len(), 1);
+        siv.runner(Box::new(Idle)).process_events();
+
+        let names = siv
+            .call_on_name(
+                "table",
+                |table: &mut TableView<SimpleItem, SimpleColumn>| {
+                    table
+                        .borrow_items()
+                        .iter()
+                        .map(|item| item.name.clone())
+                        .collect::<Vec<_>>()
+                },
+            )
+            .unwrap();
+        assert_eq!(names, vec!["1 updated", "2", "3", "4"]);
+
+        handle.clear();
+        siv.runner(Box::new(Idle)).process_events();
+        assert_eq!(
+            siv.call_on_name(
+                "table",
+                |table: &mut TableView<SimpleItem, SimpleColumn>| { table.len() }
+            ),
+            Some(0)
+        );
+    }
+
+    #[test]
+    fn should_follow_and_evict_oldest_items() {
+        let mut simple_table = setup_test_table().follow(true).max_items(5);
+
+        for i in 0..8 {
+            simple_table.insert_item(SimpleItem {
+                name: format!("{} Name", i),
+            });
         }
 
-        let focus = self.focus;
+        assert_eq!(simple_table.len(), 5);
+        assert_eq!(
+            simple_table.borrow_item(ItemIndex(0)).unwrap().name,
+            "3 Name"
+        );
+        assert_eq!(simple_table.focused_row(), Some(RowIndex(4)));
+        assert_eq!(simple_table.focused_item(), Some(ItemIndex(4)));
 
-        if self.column_select {
-            EventResult::Consumed(None)
-        } else if !self.is_empty() && last_focus != focus {
-            self.on_focus_change()
-        } else {
-            EventResult::Ignored
+        // Moving the focus away from the last row pauses following
+        simple_table.focus_row(RowIndex(1));
+        simple_table.insert_item(SimpleItem {
+            name: format!("{} Name", 8),
+        });
+
+        assert_eq!(simple_table.focused_row(), Some(RowIndex(0)));
+        assert_eq!(
+            simple_table
+                .borrow_item(simple_table.focused_item().unwrap())
+                .unwrap()
+                .name,
+            "4 Name"
+        );
+    }
+
+    #[test]
+    fn should_evict_items_down_to_capacity() {
+        let mut simple_table = setup_test_table().max_items(16);
+
+        for i in 0..17 {
+            simple_table.insert_item(SimpleItem {
+                name: format!("{:02}", i),
+            });
+        }
+        assert_eq!(simple_table.len(), 16);
+        assert_eq!(simple_table.borrow_item(ItemIndex(0)).unwrap().name, "01");
+
+        for i in 17..19 {
+            simple_table.insert_item(SimpleItem {
+                name: format!("{:02}", i),
+            });
         }
+        assert_eq!(simple_table.len(), 16);
+        assert_eq!(simple_table.borrow_item(ItemIndex(0)).unwrap().name, "03");
+
+        simple_table.set_max_items(Some(4));
+        assert_eq!(simple_table.len(), 4);
+        assert_eq!(simple_table.borrow_item(ItemIndex(0)).unwrap().name, "15");
     }
 
-    fn inner_important_area(&self, size: Vec2) -> Rect {
-        Rect::from_size((0, self.focus), (size.x, 1))
+    #[test]
+    fn should_mark_changed_items_on_replace() {
+        let mut simple_table = setup_test_table().change_highlight(Duration::from_secs(60));
+
+        let items = |names: &[&str]| {
+            names
+                .iter()
+                .map(|name| SimpleItem {
+                    name: name.to_string(),
+                })
+                .collect()
+        };
+
+        simple_table.replace_items(items(&["a", "b"]));
+        assert!(simple_table.changes.is_empty());
+
+        simple_table.replace_items(items(&["a", "b", "c"]));
+        assert_eq!(simple_table.changes.len(), 1);
+        assert!(simple_table.changes.contains_key(&2));
+    }
+
+    #[test]
+    fn should_flatten_expanded_tree_items() {
+        let mut simple_table = setup_test_table().tree();
+
+        simple_table.set_items(
+            ["b", "a/y", "a", "a/x"]
+                .iter()
+                .map(|name| SimpleItem {
+                    name: name.to_string(),
+                })
+                .collect(),
+        );
+
+        let names = |table: &TableView<SimpleItem, SimpleColumn>| -> Vec<String> {
+            table
+                .rows_to_items
+                .iter()
+                .map(|index| table.borrow_item(ItemIndex(*index)).unwrap().name.clone())
+                .collect()
+        };
+        assert_eq!(names(&simple_table), vec!["a", "b"]);
+
+        simple_table.expand_item(ItemIndex(2));
+        assert_eq!(names(&simple_table), vec!["a", "a/x", "a/y", "b"]);
+
+        // Collapsing the parent of the selected item selects the parent
+        simple_table.focus_item(ItemIndex(1));
+        simple_table.collapse_item(ItemIndex(2));
+        assert_eq!(names(&simple_table), vec!["a", "b"]);
+        assert_eq!(simple_table.focused_item(), Some(ItemIndex(2)));
+    }
+
+    #[test]
+    fn should_group_items_by_column() {
+        let mut simple_table = setup_test_table().group_by(SimpleColumn::Name);
+
+        simple_table.set_items(
+            ["b", "a", "a", "c"]
+                .iter()
+                .map(|name| SimpleItem {
+                    name: name.to_string(),
+                })
+                .collect(),
+        );
+
+        assert_eq!(simple_table.rows_to_items.len(), 7);
+        assert_eq!(simple_table.group(), Some("a"));
+        assert_eq!(simple_table.focused_item(), None);
+
+        simple_table.focus_row(RowIndex(1));
+        assert_eq!(simple_table.focused_item(), Some(ItemIndex(1)));
+
+        // Collapsing a group selects its header
+        simple_table.collapse_group("a");
+        assert_eq!(simple_table.rows_to_items.len(), 5);
+        assert_eq!(simple_table.group(), Some("a"));
+
+        // Removing the grouping keeps the focus on the selected item
+        simple_table.focus_item(ItemIndex(3));
+        simple_table.set_group_by(None);
+        assert_eq!(simple_table.rows_to_items.len(), 4);
+        assert_eq!(simple_table.focused_item(), Some(ItemIndex(3)));
+        assert_ne!(simple_table.focused_row(), Some(RowIndex(0)));
+    }
+
+    #[test]
+    fn should_aggregate_column_values() {
+        let items: Vec<SimpleItem> = ["1", "2", "2", "x"]
+            .iter()
+            .map(|name| SimpleItem {
+                name: name.to_string(),
+            })
+            .collect();
+        let items: Vec<&SimpleItem> = items.iter().collect();
+
+        let column = TableColumn::new(SimpleColumn::Name, String::new());
+        let apply = |aggregate: TableAggregate<SimpleItem>| aggregate.apply(&items, &column);
+        assert_eq!(apply(TableAggregate::Sum), "5");
+        assert_eq!(apply(TableAggregate::Average), "1.67");
+        assert_eq!(apply(TableAggregate::Min), "1");
+        assert_eq!(apply(TableAggregate::Max), "2");
+        assert_eq!(apply(TableAggregate::Count), "4");
+        assert_eq!(apply(TableAggregate::CountDistinct), "3");
+        assert_eq!(
+            apply(TableAggregate::custom(|items| format!(
+                "{} items",
+                items.len()
+            ))),
+            "4 items"
+        );
+    }
+
+    #[test]
+    fn should_order_and_format_cell_values() {
+        let mut values = vec![
+            CellValue::Empty,
+            CellValue::Float(2.5),
+            CellValue::Int(10),
+            CellValue::Bytes(1),
+            CellValue::Text("a".to_string()),
+        ];
+        values.sort();
+        assert_eq!(
+            values,
+            vec![
+                CellValue::Bytes(1),
+                CellValue::Float(2.5),
+                CellValue::Int(10),
+                CellValue::Text("a".to_string()),
+                CellValue::Empty,
+            ]
+        );
+
+        assert_eq!(CellValue::Bytes(1536).to_string(), "1.5 KiB");
+        assert_eq!(
+            CellValue::Duration(Duration::from_secs(3725)).to_string(),
+            "1h 02m 05s"
+        );
+        assert_eq!(
+            CellValue::Timestamp(std::time::UNIX_EPOCH + Duration::from_secs(951_827_696))
+                .to_string(),
+            "2000-02-29 12:34:56"
+        );
+        assert!(CellValue::Int(1).is_numeric());
+        assert!(!CellValue::Text("1".to_string()).is_numeric());
+    }
+
+    #[test]
+    fn should_use_column_closures() {
+        let mut simple_table = TableView::<SimpleItem, SimpleColumn>::new().column(
+            SimpleColumn::Name,
+            "Length",
+            |c| {
+                c.display(|item: &SimpleItem| item.name.len().to_string())
+                    .compare(|a: &SimpleItem, b: &SimpleItem| a.name.len().cmp(&b.name.len()))
+                    .aggregate(TableAggregate::Sum)
+            },
+        );
+
+        simple_table.set_items(
+            ["ccc", "a", "bb"]
+                .iter()
+                .map(|name| SimpleItem {
+                    name: name.to_string(),
+                })
+                .collect(),
+        );
+        simple_table.sort_by(SimpleColumn::Name, Ordering::Less);
+        assert_eq!(simple_table.rows_to_items, vec![1, 2, 0]);
+
+        simple_table.update_footer();
+        assert_eq!(simple_table.columns[0].footer, "6");
+    }
+
+    #[test]
+    fn should_sort_and_update_dynamic_table_columns() {
+        let mut table = DynamicTable::new()
+            .column("Name", |c| c)
+            .column("Size", |c| c)
+            .rows(vec![
+                vec![CellValue::from("a"), CellValue::Bytes(2048)],
+                vec![CellValue::from("b"), CellValue::Empty],
+                vec![CellValue::from("c"), CellValue::Int(10)],
+            ]);
+
+        table.sort_by("Size", Ordering::Less);
+        assert_eq!(table.get_inner().rows_to_items, vec![2, 0, 1]);
+        assert_eq!(table.order(), Some(("Size", Ordering::Less)));
+
+        table.remove_column("Name");
+        table.add_column("Kind", |c| c);
+        table.insert_row(vec![CellValue::Int(1), CellValue::from("file")]);
+        assert_eq!(table.column_names(), vec!["Size", "Kind"]);
+        assert_eq!(
+            table.row_values(ItemIndex(3)),
+            Some(vec![CellValue::Int(1), CellValue::from("file")])
+        );
+        assert_eq!(table.value(ItemIndex(0), "Kind"), None);
+        assert_eq!(table.value(ItemIndex(0), "Name"), None);
+        assert_eq!(table.value(ItemIndex(1), "Size"), None);
+        assert_eq!(table.value(ItemIndex(2), "Size"), Some(&CellValue::Int(10)));
+
+        // The id of the removed "Name" column is reused for "Kind"
+        table.sort_by("Kind", Ordering::Less);
+        assert_eq!(table.get_inner().order(), Some((0, Ordering::Less)));
+        table.remove_column("Size");
+        table.add_column("Size", |c| c);
+        table.sort_by("Size", Ordering::Less);
+        assert_eq!(table.get_inner().order(), Some((1, Ordering::Less)));
+    }
+
+    #[test]
+    fn should_keep_empty_cells_last_when_sorting_descending() {
+        let mut table = DynamicTable::new().column("Size", |c| c).rows(vec![
+            vec![CellValue::Int(1)],
+            vec![CellValue::Empty],
+            vec![CellValue::Int(3)],
+            vec![CellValue::Int(2)],
+        ]);
+
+        table.sort_by("Size", Ordering::Greater);
+        assert_eq!(table.get_inner().rows_to_items, vec![2, 3, 0, 1]);
+
+        table.sort_by("Size", Ordering::Less);
+        assert_eq!(table.get_inner().rows_to_items, vec![0, 3, 2, 1]);
     }
 
-    fn on_submit_event(&mut self) -> EventResult {
-        if let Some(ref cb) = &self.on_submit {
-            let cb = Rc::clone(cb);
-            let row = self.row().unwrap();
-            let index = self.item().unwrap();
-            return EventResult::Consumed(Some(Callback::from_fn(move |s| cb(s, row, index))));
+    #[test]
+    fn should_support_string_columns_and_unclonable_items() {
+        struct Entry {
+            values: HashMap<String, String>,
+            _lock: std::sync::Mutex<()>,
+        }
+
+        impl TableViewItem<String> for Entry {
+            fn to_column(&self, column: String) -> String {
+                self.values.get(&column).cloned().unwrap_or_default()
+            }
         }
-        EventResult::Ignored
+
+        let entry = |name: &str| Entry {
+            values: vec![("name".to_string(), name.to_string())]
+                .into_iter()
+                .collect(),
+            _lock: std::sync::Mutex::new(()),
+        };
+
+        let mut table = TableView::<Entry, String>::new().column("name".to_string(), "Name", |c| c);
+        table.set_items(vec![entry("b"), entry("c"), entry("a")]);
+        table.sort_by("name".to_string(), Ordering::Greater);
+        assert_eq!(table.rows_to_items, vec![1, 0, 2]);
+        assert_eq!(table.order(), Some(("name".to_string(), Ordering::Greater)));
     }
-}
 
-impl<T: TableViewItem<H> + 'static, H: Eq + Hash + Copy + Clone + 'static> View
-    for TableView<T, H>
-{
-    fn draw(&self, printer: &Printer) {
-        self.draw_columns(printer, "╷ ", |printer, column| {
-            let color = if self.enabled && (column.order != Ordering::Equal || column.selected) {
-                if self.column_select && column.selected && self.enabled && printer.focused {
-                    theme::ColorStyle::highlight()
-                } else {
-                    theme::ColorStyle::highlight_inactive()
-                }
-            } else {
-                theme::ColorStyle::primary()
-            };
+    #[test]
+    fn should_convert_between_rows_and_items() {
+        let mut simple_table = setup_test_table();
+        simple_table.set_items(
+            ["c", "a", "b"]
+                .iter()
+                .map(|name| SimpleItem {
+                    name: name.to_string(),
+                })
+                .collect(),
+        );
+        simple_table.sort_by(SimpleColumn::Name, Ordering::Less);
 
-            printer.with_color(color, |printer| {
-                column.draw_header(printer);
-            });
+        assert_eq!(simple_table.item_for_row(RowIndex(0)), Some(ItemIndex(1)));
+        assert_eq!(simple_table.row_for_item(ItemIndex(0)), Some(RowIndex(2)));
+        assert_eq!(simple_table.item_for_row(RowIndex(3)), None);
+
+        simple_table.focus_item(ItemIndex(2));
+        assert_eq!(simple_table.focused_row(), Some(RowIndex(1)));
+    }
+
+    #[test]
+    fn should_emit_table_events() {
+        use std::cell::RefCell;
+
+        let events = Rc::new(RefCell::new(Vec::new()));
+        let log = Rc::clone(&events);
+        let mut simple_table = setup_test_table().on_event(move |_, event| {
+            log.borrow_mut()
+                .push((event.kind, event.item, event.column));
         });
+        simple_table.layout(Vec2::new(40, 10));
 
-        self.draw_columns(
-            &printer.offset((0, 1)).focused(true),
-            "┴─",
-            |printer, column| {
-                printer.print_hline((0, 0), column.width + 1, "─");
-            },
+        let mut siv = Cursive::new();
+        let mut send = |table: &mut TableView<SimpleItem, SimpleColumn>, key: Key| {
+            View::on_event(table, Event::Key(key)).process(&mut siv);
+        };
+
+        send(&mut simple_table, Key::Enter);
+        simple_table.set_items(
+            ["a", "b"]
+                .iter()
+                .map(|name| SimpleItem {
+                    name: name.to_string(),
+                })
+                .collect(),
+        );
+        simple_table.layout(Vec2::new(40, 10));
+        send(&mut simple_table, Key::Down);
+        send(&mut simple_table, Key::Enter);
+        send(&mut simple_table, Key::Right);
+        send(&mut simple_table, Key::Enter);
+
+        assert_eq!(
+            *events.borrow(),
+            vec![
+                (TableEventKind::SubmitEmpty, None, None),
+                (TableEventKind::Select, Some(ItemIndex(1)), None),
+                (TableEventKind::Submit, Some(ItemIndex(1)), None),
+                (
+                    TableEventKind::ColumnSelect,
+                    Some(ItemIndex(1)),
+                    Some(SimpleColumn::Name)
+                ),
+                (
+                    TableEventKind::Sort(Ordering::Greater),
+                    Some(ItemIndex(1)),
+                    Some(SimpleColumn::Name)
+                ),
+            ]
         );
+    }
 
-        // Extend the vertical bars to the end of the view
-        for y in 2..printer.size.y {
-            self.draw_columns(&printer.offset((0, y)), "┆ ", |_, _| ());
-        }
+    #[test]
+    fn should_page_by_viewport_height() {
+        let mut simple_table = setup_test_table()
+            .keymap(TableKeymap::vim())
+            .scrolloff(2)
+            .wrap_around(true);
+        simple_table.set_items(
+            (0..30)
+                .map(|i| SimpleItem {
+                    name: format!("{:02}", i),
+                })
+                .collect(),
+        );
+        simple_table.layout(Vec2::new(40, 10));
+        assert_eq!(simple_table.visible_rows(), 0..8);
 
-        let printer = &printer.offset((0, 2)).focused(true);
-        scroll::draw(self, printer, Self::draw_content);
+        let send = |table: &mut TableView<SimpleItem, SimpleColumn>, event: Event| {
+            View::on_event(table, event);
+            table.focused_row().unwrap().0
+        };
+
+        assert_eq!(send(&mut simple_table, Event::Key(Key::PageDown)), 8);
+        assert_eq!(simple_table.visible_rows(), 3..11);
+        assert_eq!(send(&mut simple_table, Event::CtrlChar('u')), 4);
+        assert_eq!(simple_table.visible_rows(), 2..10);
+        assert_eq!(send(&mut simple_table, Event::CtrlChar('d')), 8);
+        assert_eq!(send(&mut simple_table, Event::Key(Key::Home)), 0);
+        assert_eq!(send(&mut simple_table, Event::Key(Key::Up)), 29);
+        assert_eq!(simple_table.visible_rows(), 22..30);
+        assert_eq!(send(&mut simple_table, Event::Key(Key::Down)), 0);
     }
 
-    fn layout(&mut self, size: Vec2) {
-        scroll::layout(
-            self,
-            size.saturating_sub((0, 2)),
-            self.needs_relayout,
-            Self::layout_content,
-            Self::content_required_size,
+    #[test]
+    fn should_show_details_of_focused_item() {
+        use cursive::views::TextView;
+
+        let mut table = setup_test_table();
+        table.set_items(
+            ["a", "b"]
+                .iter()
+                .map(|name| SimpleItem {
+                    name: name.to_string(),
+                })
+                .collect(),
         );
-    }
+        let mut view =
+            MasterDetailView::with_text(table, |item: &SimpleItem| format!("Item {}", item.name))
+                .ratio(0.6);
+        view.layout(Vec2::new(40, 21));
+
+        let detail = |view: &mut MasterDetailView<SimpleItem, SimpleColumn>| {
+            view.get_detail_mut()
+                .as_any_mut()
+                .downcast_mut::<TextView>()
+                .map(|text| text.get_content().source().to_string())
+        };
 
-    fn take_focus(&mut self, _: Direction) -> bool {
-        self.enabled
+        assert_eq!(detail(&mut view), Some("Item a".to_string()));
+        view.on_event(Event::Key(Key::Down));
+        assert_eq!(detail(&mut view), Some("Item b".to_string()));
+        assert_eq!(view.get_table().focused_item(), Some(ItemIndex(1)));
+
+        view.get_table_mut().clear();
+        view.layout(Vec2::new(40, 21));
+        assert_eq!(detail(&mut view), None);
     }
 
-    fn on_event(&mut self, event: Event) -> EventResult {
-        if !self.enabled {
-            return EventResult::Ignored;
-        }
+    #[test]
+    fn should_expand_detail_rows() {
+        use cursive::views::TextView;
+        use std::cell::RefCell;
+
+        let mut simple_table = setup_test_table().detail_view(|item: &SimpleItem| {
+            Box::new(TextView::new(format!("{}\ndetails", item.name)))
+        });
+        simple_table.set_items(
+            (0..10)
+                .map(|i| SimpleItem {
+                    name: format!("{}", i),
+                })
+                .collect(),
+        );
+        simple_table.layout(Vec2::new(40, 10));
+        assert_eq!(simple_table.visible_rows(), 0..8);
+
+        // Without a checkbox column, Space expands the focused item below its
+        // row and is still reported as an action
+        let mut siv = Cursive::new();
+        let actions = Rc::new(RefCell::new(Vec::new()));
+        let log = Rc::clone(&actions);
+        simple_table.set_on_event(move |_, event| log.borrow_mut().push(event.kind));
+        View::on_event(&mut simple_table, Event::Char(' ')).process(&mut siv);
+        simple_table.layout(Vec2::new(40, 10));
+        assert!(simple_table.is_detail_expanded(ItemIndex(0)));
+        assert_eq!(
+            *actions.borrow(),
+            vec![TableEventKind::Action(TableAction::ToggleSelect)]
+        );
 
-        match event {
+        // `d` toggles it as well
+        View::on_event(&mut simple_table, Event::Char('d'));
+        assert!(!simple_table.is_detail_expanded(ItemIndex(0)));
+        View::on_event(&mut simple_table, Event::Char('d'));
+        simple_table.layout(Vec2::new(40, 10));
+        assert!(simple_table.is_detail_expanded(ItemIndex(0)));
+        assert_eq!(simple_table.visible_rows(), 0..6);
+
+        // Clicking the marker of the second row, which is drawn below the detail
+        View::on_event(
+            &mut simple_table,
             Event::Mouse {
-                position,
-                offset,
+                offset: Vec2::zero(),
+                position: Vec2::new(0, 5),
                 event: MouseEvent::Press(MouseButton::Left),
-            } if position.checked_sub(offset).map_or(false, |p| p.y == 0) => {
-                if let Some(position) = position.checked_sub(offset) {
-                    if let Some(col) = self.column_for_x(position.x) {
-                        if self.column_select && self.columns[col].selected {
-                            return self.column_select();
-                        } else {
-                            let active = self.active_column();
-                            self.columns[active].selected = false;
-                            self.columns[col].selected = true;
-                            self.column_select = true;
-                        }
-                    }
-                }
-                EventResult::Ignored
-            }
-            event => scroll::on_event(
-                self,
-                event.relativized((0, 2)),
-                Self::on_inner_event,
-                Self::inner_important_area,
-            ),
-        }
-    }
+            },
+        )
+        .process(&mut siv);
+        simple_table.layout(Vec2::new(40, 10));
+        assert!(simple_table.is_detail_expanded(ItemIndex(1)));
+        assert_eq!(simple_table.focused_row(), Some(RowIndex(1)));
+        assert_eq!(actions.borrow().last(), Some(&TableEventKind::Select));
+        assert_eq!(simple_table.visible_rows(), 0..4);
+
+        // Clicks within a detail row do not move the focus
+        View::on_event(
+            &mut simple_table,
+            Event::Mouse {
+                offset: Vec2::zero(),
+                position: Vec2::new(4, 3),
+                event: MouseEvent::Press(MouseButton::Left),
+            },
+        );
+        assert_eq!(simple_table.focused_row(), Some(RowIndex(1)));
 
-    fn important_area(&self, size: Vec2) -> Rect {
-        self.inner_important_area(size.saturating_sub((0, 2))) + (0, 2)
+        simple_table.remove_item(ItemIndex(0));
+        assert!(simple_table.is_detail_expanded(ItemIndex(0)));
+        simple_table.collapse_all_details();
+        simple_table.layout(Vec2::new(40, 10));
+        assert_eq!(simple_table.visible_rows(), 0..8);
     }
-}
 
-/// A type used for the construction of columns in a
-/// [`TableView`](struct.TableView.html).
-pub struct TableColumn<H> {
-    column: H,
-    title: String,
-    selected: bool,
-    alignment: HAlign,
-    order: Ordering,
-    width: usize,
-    default_order: Ordering,
-    requested_width: Option<TableColumnWidth>,
-}
+    #[test]
+    fn should_route_events_to_cell_views() {
+        use cursive::views::Button;
 
-enum TableColumnWidth {
-    Percent(usize),
-    Absolute(usize),
-}
+        let mut simple_table =
+            TableView::<SimpleItem, SimpleColumn>::new().column(SimpleColumn::Name, "Name", |c| {
+                c.width(10)
+                    .view(|item: &SimpleItem| Box::new(Button::new(item.name.clone(), |_| ())))
+            });
+        simple_table.set_items(
+            (0..3)
+                .map(|i| SimpleItem {
+                    name: format!("{}", i),
+                })
+                .collect(),
+        );
+        simple_table.layout(Vec2::new(40, 10));
+        assert_eq!(simple_table.cell_views.len(), 3);
+
+        let click = |table: &mut TableView<SimpleItem, SimpleColumn>, event: MouseEvent| {
+            View::on_event(
+                table,
+                Event::Mouse {
+                    offset: Vec2::zero(),
+                    position: Vec2::new(4, 3),
+                    event,
+                },
+            )
+        };
 
-impl<H: Copy + Clone + 'static> TableColumn<H> {
-    /// Sets the default ordering of the column.
-    pub fn ordering(mut self, order: Ordering) -> Self {
-        self.default_order = order;
-        self
+        // Pressing a button focuses its row, releasing it triggers the button
+        click(&mut simple_table, MouseEvent::Press(MouseButton::Left));
+        assert_eq!(simple_table.focused_row(), Some(RowIndex(1)));
+        assert_eq!(simple_table.active_cell(), Some(0));
+        assert!(matches!(
+            click(&mut simple_table, MouseEvent::Release(MouseButton::Left)),
+            EventResult::Consumed(Some(_))
+        ));
+
+        // Keys reach the button until its cell is left via <Tab>
+        assert!(matches!(
+            View::on_event(&mut simple_table, Event::Key(Key::Enter)),
+            EventResult::Consumed(Some(_))
+        ));
+        assert!(!View::on_event(&mut simple_table, Event::Key(Key::Tab)).is_consumed());
+        assert_eq!(simple_table.active_cell(), None);
+        assert!(!View::on_event(&mut simple_table, Event::Key(Key::Enter)).is_consumed());
+
+        simple_table.remove_item(ItemIndex(0));
+        simple_table.layout(Vec2::new(40, 10));
+        assert_eq!(simple_table.cell_views.len(), 2);
     }
 
-    /// Sets the horizontal text alignment of the column.
-    pub fn align(mut self, alignment: HAlign) -> Self {
-        self.alignment = alignment;
-        self
+    #[test]
+    fn should_toggle_checkboxes() {
+        use std::cell::RefCell;
+
+        let checks = Rc::new(RefCell::new(Vec::new()));
+        let log = Rc::clone(&checks);
+        let mut simple_table = TableView::<SimpleItem, SimpleColumn>::new()
+            .column(SimpleColumn::Name, "All", |c| c.checkbox().width(7))
+            .on_check(move |_, item, checked| log.borrow_mut().push((item, checked)));
+        simple_table.set_items(
+            ["a", "b", "c"]
+                .iter()
+                .map(|name| SimpleItem {
+                    name: name.to_string(),
+                })
+                .collect(),
+        );
+        simple_table.layout(Vec2::new(40, 10));
+
+        let click = |table: &mut TableView<SimpleItem, SimpleColumn>, y: usize| {
+            View::on_event(
+                table,
+                Event::Mouse {
+                    offset: Vec2::zero(),
+                    position: Vec2::new(1, y),
+                    event: MouseEvent::Press(MouseButton::Left),
+                },
+            )
+        };
+
+        let mut siv = Cursive::new();
+        View::on_event(&mut simple_table, Event::Char(' ')).process(&mut siv);
+        click(&mut simple_table, 4).process(&mut siv);
+        assert_eq!(simple_table.focused_row(), Some(RowIndex(2)));
+        assert_eq!(
+            simple_table.checked_items(),
+            vec![ItemIndex(0), ItemIndex(2)]
+        );
+
+        // The header checks all remaining rows, then unchecks all of them
+        click(&mut simple_table, 0).process(&mut siv);
+        assert_eq!(simple_table.checked_items().len(), 3);
+        click(&mut simple_table, 0).process(&mut siv);
+        assert!(simple_table.checked_items().is_empty());
+
+        assert_eq!(
+            *checks.borrow(),
+            vec![
+                (ItemIndex(0), true),
+                (ItemIndex(2), true),
+                (ItemIndex(1), true),
+                (ItemIndex(0), false),
+                (ItemIndex(1), false),
+                (ItemIndex(2), false),
+            ]
+        );
+
+        simple_table.set_all_checked(true);
+        simple_table.remove_item(ItemIndex(1));
+        assert_eq!(
+            simple_table.checked_items(),
+            vec![ItemIndex(0), ItemIndex(1)]
+        );
     }
 
-    /// Sets how many characters of width this column will try to occupy.
-    pub fn width(mut self, width: usize) -> Self {
-        self.requested_width = Some(TableColumnWidth::Absolute(width));
-        self
+    #[test]
+    fn should_toggle_checkboxes_and_details_separately() {
+        use cursive::views::TextView;
+        use std::cell::RefCell;
+
+        let actions = Rc::new(RefCell::new(Vec::new()));
+        let log = Rc::clone(&actions);
+        let mut simple_table = TableView::<SimpleItem, SimpleColumn>::new()
+            .column(SimpleColumn::Name, "All", |c| c.checkbox().width(7))
+            .detail_view(|item: &SimpleItem| Box::new(TextView::new(item.name.clone())))
+            .on_event(move |_, event| log.borrow_mut().push(event.kind));
+        simple_table.set_items(vec![SimpleItem {
+            name: "a".to_string(),
+        }]);
+        simple_table.layout(Vec2::new(40, 10));
+
+        let mut siv = Cursive::new();
+        View::on_event(&mut simple_table, Event::Char(' ')).process(&mut siv);
+        assert_eq!(simple_table.checked_items(), vec![ItemIndex(0)]);
+        assert!(!simple_table.is_detail_expanded(ItemIndex(0)));
+
+        View::on_event(&mut simple_table, Event::Char('d')).process(&mut siv);
+        assert_eq!(simple_table.checked_items(), vec![ItemIndex(0)]);
+        assert!(simple_table.is_detail_expanded(ItemIndex(0)));
+
+        assert_eq!(
+            *actions.borrow(),
+            vec![TableEventKind::Action(TableAction::ToggleSelect)]
+        );
     }
 
-    /// Sets what percentage of the width of the entire table this column will
-    /// try to occupy.
-    pub fn width_percent(mut self, width: usize) -> Self {
-        self.requested_width = Some(TableColumnWidth::Percent(width));
-        self
+    #[test]
+    fn should_link_tables_by_key() {
+        use cursive::views::NamedView;
+
+        let items = |names: &[&str]| {
+            names
+                .iter()
+                .map(|name| SimpleItem {
+                    name: name.to_string(),
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let mut siv = Cursive::new();
+        for (name, names) in [("left", ["a", "b", "c"]), ("right", ["b", "c", "a"])] {
+            let mut table = setup_test_table().items(items(&names));
+            table.layout(Vec2::new(40, 10));
+            siv.add_layer(NamedView::new(name, table));
+        }
+
+        TableLink::<SimpleItem, SimpleColumn>::new()
+            .table("left")
+            .table("right")
+            .follow_keys()
+            .install(&mut siv);
+
+        let send = |siv: &mut Cursive, name: &str, event: Event| {
+            siv.call_on_name(name, |table: &mut TableView<SimpleItem, SimpleColumn>| {
+                View::on_event(table, event)
+            })
+            .unwrap()
+            .process(siv);
+        };
+        let focus = |siv: &mut Cursive, name: &str| {
+            siv.call_on_name(name, |table: &mut TableView<SimpleItem, SimpleColumn>| {
+                table.focused_item().unwrap()
+            })
+            .unwrap()
+        };
+
+        send(&mut siv, "left", Event::Key(Key::Down));
+        assert_eq!(focus(&mut siv, "right"), ItemIndex(0));
+        send(&mut siv, "right", Event::Key(Key::Down));
+        assert_eq!(focus(&mut siv, "left"), ItemIndex(2));
     }
 
-    fn new(column: H, title: String) -> Self {
-        Self {
-            column,
-            title,
-            selected: false,
-            alignment: HAlign::Left,
-            order: Ordering::Equal,
-            width: 0,
-            default_order: Ordering::Less,
-            requested_width: None,
+    #[test]
+    fn should_link_tables_of_different_lengths_by_row() {
+        use cursive::views::NamedView;
+        use std::cell::RefCell;
+
+        let selected = Rc::new(RefCell::new(Vec::new()));
+        let log = Rc::clone(&selected);
+
+        let mut siv = Cursive::new();
+        for (name, len) in [("long", 3), ("short", 1)] {
+            let mut table = setup_test_table().items(
+                (0..len)
+                    .map(|i| SimpleItem {
+                        name: i.to_string(),
+                    })
+                    .collect(),
+            );
+            if name == "long" {
+                let log = Rc::clone(&log);
+                table.set_on_select_item(move |_, row, _| log.borrow_mut().push(row));
+            }
+            table.layout(Vec2::new(40, 10));
+            siv.add_layer(NamedView::new(name, table));
+        }
+
+        TableLink::<SimpleItem, SimpleColumn>::new()
+            .table("long")
+            .table("short")
+            .follow_rows()
+            .install(&mut siv);
+
+        for _ in 0..2 {
+            siv.call_on_name("long", |table: &mut TableView<SimpleItem, SimpleColumn>| {
+                View::on_event(table, Event::Key(Key::Down))
+            })
+            .unwrap()
+            .process(&mut siv);
         }
+
+        // Callbacks set before installing the link are still called
+        assert_eq!(*selected.borrow(), vec![RowIndex(1), RowIndex(2)]);
+        siv.call_on_name(
+            "short",
+            |table: &mut TableView<SimpleItem, SimpleColumn>| {
+                assert_eq!(table.focused_row(), Some(RowIndex(0)));
+            },
+        )
+        .unwrap();
     }
 
-    fn draw_header(&self, printer: &Printer) {
-        let order = match self.order {
-            Ordering::Less => "^",
-            Ordering::Greater => "v",
-            Ordering::Equal => " ",
+    #[test]
+    fn should_scroll_without_moving_focus() {
+        use std::cell::RefCell;
+
+        let offsets = Rc::new(RefCell::new(Vec::new()));
+        let log = Rc::clone(&offsets);
+        let mut simple_table =
+            setup_test_table().on_scroll(move |_, offset| log.borrow_mut().push(offset));
+        simple_table.set_items(
+            (0..30)
+                .map(|i| SimpleItem {
+                    name: format!("{:02}", i),
+                })
+                .collect(),
+        );
+        simple_table.layout(Vec2::new(40, 10));
+
+        simple_table.scroll_to_row(RowIndex(20), ScrollAlign::Center);
+        assert_eq!(simple_table.scroll_offset(), 16);
+        simple_table.scroll_to_row(RowIndex(20), ScrollAlign::Bottom);
+        assert_eq!(simple_table.first_visible_row(), Some(RowIndex(13)));
+        simple_table.scroll_to_row(RowIndex(16), ScrollAlign::Nearest);
+        assert_eq!(simple_table.scroll_offset(), 13);
+        simple_table.scroll_to_row(RowIndex(5), ScrollAlign::Nearest);
+        assert_eq!(simple_table.scroll_offset(), 5);
+        simple_table.scroll_to_row(RowIndex(25), ScrollAlign::Top);
+        assert_eq!(simple_table.scroll_offset(), 22);
+        simple_table.set_scroll_offset(0);
+        assert_eq!(simple_table.focused_row(), Some(RowIndex(0)));
+        assert!(offsets.borrow().is_empty());
+
+        let mut siv = Cursive::new();
+        let wheel = Event::Mouse {
+            offset: Vec2::zero(),
+            position: Vec2::new(3, 4),
+            event: MouseEvent::WheelDown,
         };
+        View::on_event(&mut simple_table, wheel).process(&mut siv);
+        View::on_event(&mut simple_table, Event::Key(Key::Home)).process(&mut siv);
+        assert_eq!(*offsets.borrow(), vec![3, 0]);
+    }
 
-        let header = match self.alignment {
-            HAlign::Left => format!(
-                "{:<width$} [{}]",
-                self.title,
-                order,
-                width = self.width.saturating_sub(4)
-            ),
-            HAlign::Right => format!(
-                "{:>width$} [{}]",
-                self.title,
-                order,
-                width = self.width.saturating_sub(4)
-            ),
-            HAlign::Center => format!(
-                "{:^width$} [{}]",
-                self.title,
-                order,
-                width = self.width.saturating_sub(4)
-            ),
+    #[test]
+    fn should_use_custom_keymaps() {
+        use std::cell::RefCell;
+
+        let actions = Rc::new(RefCell::new(Vec::new()));
+        let log = Rc::clone(&actions);
+        let mut simple_table =
+            setup_test_table()
+                .keymap(TableKeymap::vim())
+                .on_event(move |_, event| {
+                    if let TableEventKind::Action(action) = event.kind {
+                        log.borrow_mut().push(action);
+                    }
+                });
+        simple_table.set_items(
+            ["a", "b", "c"]
+                .iter()
+                .map(|name| SimpleItem {
+                    name: name.to_string(),
+                })
+                .collect(),
+        );
+        simple_table.layout(Vec2::new(40, 10));
+
+        let mut siv = Cursive::new();
+        let mut send = |table: &mut TableView<SimpleItem, SimpleColumn>, event: Event| {
+            View::on_event(table, event).process(&mut siv);
         };
 
-        printer.print((0, 0), header.as_str());
+        send(&mut simple_table, Event::Char('j'));
+        assert_eq!(simple_table.focused_row(), Some(RowIndex(1)));
+        send(&mut simple_table, Event::Char('G'));
+        assert_eq!(simple_table.focused_row(), Some(RowIndex(2)));
+        send(&mut simple_table, Event::Char('k'));
+        assert_eq!(simple_table.focused_row(), Some(RowIndex(1)));
+        send(&mut simple_table, Event::Char('g'));
+        assert_eq!(simple_table.focused_row(), Some(RowIndex(0)));
+
+        simple_table
+            .keymap_mut()
+            .set_binding(Event::Char('x'), TableAction::ToggleSelect);
+        simple_table.keymap_mut().remove_binding(&Event::Char('j'));
+        send(&mut simple_table, Event::Char('j'));
+        assert_eq!(simple_table.focused_row(), Some(RowIndex(0)));
+        send(&mut simple_table, Event::Char('x'));
+        send(&mut simple_table, Event::Char('/'));
+
+        assert_eq!(
+            *actions.borrow(),
+            vec![TableAction::ToggleSelect, TableAction::Filter]
+        );
     }
 
-    fn draw_row(&self, printer: &Printer, value: &str) {
-        let value = match self.alignment {
-            HAlign::Left => format!("{:<width$} ", value, width = self.width),
-            HAlign::Right => format!("{:>width$} ", value, width = self.width),
-            HAlign::Center => format!("{:^width$} ", value, width = self.width),
+    #[test]
+    fn should_detect_double_clicks() {
+        use std::cell::RefCell;
+
+        let events = Rc::new(RefCell::new(Vec::new()));
+        let log = Rc::clone(&events);
+        let mut simple_table = setup_test_table()
+            .submit_mode(SubmitMode::DoubleClick)
+            .on_event(move |_, event| log.borrow_mut().push((event.kind, event.item)));
+        simple_table.set_items(
+            ["a", "b"]
+                .iter()
+                .map(|name| SimpleItem {
+                    name: name.to_string(),
+                })
+                .collect(),
+        );
+        simple_table.layout(Vec2::new(40, 10));
+
+        let mut siv = Cursive::new();
+        let mut click = |table: &mut TableView<SimpleItem, SimpleColumn>, button, y: usize| {
+            let event = Event::Mouse {
+                offset: Vec2::zero(),
+                position: Vec2::new(3, 2 + y),
+                event: MouseEvent::Press(button),
+            };
+            View::on_event(table, event).process(&mut siv);
         };
 
-        printer.print((0, 0), value.as_str());
+        click(&mut simple_table, MouseButton::Left, 0);
+        click(&mut simple_table, MouseButton::Left, 1);
+        click(&mut simple_table, MouseButton::Left, 1);
+        simple_table.set_submit_mode(SubmitMode::Never);
+        click(&mut simple_table, MouseButton::Left, 1);
+        click(&mut simple_table, MouseButton::Left, 1);
+        click(&mut simple_table, MouseButton::Middle, 0);
+
+        assert_eq!(
+            *events.borrow(),
+            vec![
+                (TableEventKind::Select, Some(ItemIndex(1))),
+                (TableEventKind::Submit, Some(ItemIndex(1))),
+                (TableEventKind::DoubleClick, Some(ItemIndex(1))),
+                (TableEventKind::DoubleClick, Some(ItemIndex(1))),
+                (TableEventKind::Select, Some(ItemIndex(0))),
+                (TableEventKind::MiddleClick, Some(ItemIndex(0))),
+            ]
+        );
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    #[test]
+    fn should_request_context_menus() {
+        use std::cell::RefCell;
+
+        let requests = Rc::new(RefCell::new(Vec::new()));
+        let log = Rc::clone(&requests);
+        let mut simple_table =
+            setup_test_table().on_context_menu(move |_, row, item, column, position| {
+                log.borrow_mut().push((row, item, column, position));
+            });
+        simple_table.set_items(
+            ["a", "b"]
+                .iter()
+                .map(|name| SimpleItem {
+                    name: name.to_string(),
+                })
+                .collect(),
+        );
+        simple_table.layout(Vec2::new(40, 10));
 
-    #[derive(Copy, Clone, PartialEq, Eq, Hash)]
-    enum SimpleColumn {
-        Name,
+        let mut siv = Cursive::new();
+        View::on_event(&mut simple_table, Event::Key(Key::Down)).process(&mut siv);
+        View::on_event(&mut simple_table, Event::Shift(Key::F10)).process(&mut siv);
+        View::on_event(
+            &mut simple_table,
+            Event::Mouse {
+                offset: Vec2::zero(),
+                position: Vec2::new(3, 2),
+                event: MouseEvent::Press(MouseButton::Right),
+            },
+        )
+        .process(&mut siv);
+
+        assert_eq!(simple_table.focused_row(), Some(RowIndex(0)));
+        assert_eq!(
+            *requests.borrow(),
+            vec![
+                (
+                    RowIndex(1),
+                    ItemIndex(1),
+                    SimpleColumn::Name,
+                    Vec2::new(0, 3)
+                ),
+                (
+                    RowIndex(0),
+                    ItemIndex(0),
+                    SimpleColumn::Name,
+                    Vec2::new(3, 2)
+                ),
+            ]
+        );
     }
 
-    #[allow(dead_code)]
-    impl SimpleColumn {
-        fn as_str(&self) -> &str {
-            match *self {
-                SimpleColumn::Name => "Name",
-            }
-        }
-    }
+    #[test]
+    fn should_check_cells_for_emptiness_once_per_item() {
+        use std::sync::atomic::AtomicUsize;
 
-    #[derive(Clone, Debug)]
-    struct SimpleItem {
-        name: String,
-    }
+        static CELLS: AtomicUsize = AtomicUsize::new(0);
 
-    impl TableViewItem<SimpleColumn> for SimpleItem {
-        fn to_column(&self, column: SimpleColumn) -> String {
-            match column {
-                SimpleColumn::Name => self.name.to_string(),
+        struct CountedItem(i64);
+
+        impl TableViewItem<SimpleColumn> for CountedItem {
+            fn to_column(&self, _: SimpleColumn) -> String {
+                self.0.to_string()
             }
-        }
 
-        fn cmp(&self, other: &Self, column: SimpleColumn) -> Ordering
-        where
-            Self: Sized,
-        {
-            match column {
-                SimpleColumn::Name => self.name.cmp(&other.name),
+            fn cell(&self, _: SimpleColumn) -> CellValue {
+                CELLS.fetch_add(1, AtomicOrdering::Relaxed);
+                CellValue::Int(self.0)
+            }
+
+            fn cmp(&self, other: &Self, _: SimpleColumn) -> Ordering {
+                self.0.cmp(&other.0)
             }
         }
-    }
 
-    fn setup_test_table() -> TableView<SimpleItem, SimpleColumn> {
-        TableView::<SimpleItem, SimpleColumn>::new()
-            .column(SimpleColumn::Name, "Name", |c| c.width_percent(20))
+        let mut table = TableView::<CountedItem, SimpleColumn>::new()
+            .column(SimpleColumn::Name, "Name", |c| c)
+            .items((0..64).map(|i| CountedItem((i * 37) % 64)).collect());
+        CELLS.store(0, AtomicOrdering::Relaxed);
+
+        table.sort_by(SimpleColumn::Name, Ordering::Greater);
+        assert!(CELLS.load(AtomicOrdering::Relaxed) <= 64);
+        assert_eq!(table.items[table.rows_to_items[0]].0, 63);
     }
 
     #[test]
-    fn should_insert_into_existing_table() {
-        let mut simple_table = setup_test_table();
-
-        let mut simple_items = Vec::new();
-
-        for i in 1..=10 {
-            simple_items.push(SimpleItem {
-                name: format!("{} - Name", i),
-            });
-        }
-
-        // Insert First Batch of Items
-        simple_table.set_items(simple_items);
+    fn should_request_context_menus_on_scrolled_tables() {
+        use std::cell::RefCell;
 
-        // Test for Additional item insertion
-        simple_table.insert_item(SimpleItem {
-            name: format!("{} Name", 11),
+        let requests = Rc::new(RefCell::new(Vec::new()));
+        let log = Rc::clone(&requests);
+        let mut simple_table = setup_test_table().on_context_menu(move |_, row, _, _, position| {
+            log.borrow_mut().push((row, position));
         });
+        simple_table.set_items(
+            (0..30)
+                .map(|i| SimpleItem {
+                    name: format!("{:02}", i),
+                })
+                .collect(),
+        );
+        simple_table.layout(Vec2::new(40, 10));
+        simple_table.set_scroll_offset(10);
 
-        assert!(simple_table.len() == 11);
+        let mut siv = Cursive::new();
+        View::on_event(
+            &mut simple_table,
+            Event::Mouse {
+                offset: Vec2::zero(),
+                position: Vec2::new(3, 4),
+                event: MouseEvent::Press(MouseButton::Right),
+            },
+        )
+        .process(&mut siv);
+        View::on_event(&mut simple_table, Event::Shift(Key::F10)).process(&mut siv);
+
+        // Both open at the clicked row on screen, not at its content position
+        assert_eq!(
+            *requests.borrow(),
+            vec![
+                (RowIndex(12), Vec2::new(3, 4)),
+                (RowIndex(12), Vec2::new(0, 4)),
+            ]
+        );
     }
 
     #[test]
-    fn should_insert_into_empty_table() {
+    #[cfg(feature = "rayon")]
+    fn should_sort_large_tables_in_parallel_like_serially() {
+        let names: Vec<String> = (0..PARALLEL_SORT_THRESHOLD * 2)
+            .map(|i| format!("{:03}", (i * 7919) % 997))
+            .collect();
+
         let mut simple_table = setup_test_table();
+        simple_table.set_items(
+            names
+                .iter()
+                .map(|name| SimpleItem { name: name.clone() })
+                .collect(),
+        );
 
-        // Test for First item insertion
-        simple_table.insert_item(SimpleItem {
-            name: format!("{} Name", 1),
-        });
+        for &order in &[Ordering::Less, Ordering::Greater] {
+            // Equal names must keep their previous order, just like with the
+            // stable serial sort
+            let mut expected = simple_table.rows_to_items.clone();
+            expected.sort_by(|&a, &b| match order {
+                Ordering::Greater => names[b].cmp(&names[a]),
+                _ => names[a].cmp(&names[b]),
+            });
 
-        assert!(simple_table.len() == 1);
+            simple_table.sort_by(SimpleColumn::Name, order);
+            assert_eq!(simple_table.rows_to_items, expected);
+        }
     }
 }
@@ -0,0 +1,229 @@
+// STD Dependencies -----------------------------------------------------------
+use std::hash::Hash;
+use std::marker::PhantomData;
+use std::rc::Rc;
+
+// External Dependencies ------------------------------------------------------
+use cursive::{Cursive, With};
+
+// Modules --------------------------------------------------------------------
+use super::{ItemIndex, KeyedTableViewItem, MaybeSync, RowIndex, TableView, TableViewItem};
+
+/// Function moving the focus of the target tables after the focus of the
+/// source table changed.
+///
+/// This is a private type to help readability.
+type FollowFocus = fn(&mut Cursive, &str, RowIndex, ItemIndex, &[String]);
+
+/// Links multiple named [`TableView`](struct.TableView.html)s, so that
+/// scrolling and focus changes in one of them are mirrored by the others.
+///
+/// Tables added via [`table`](#method.table) lead and follow, while tables
+/// added via [`follower`](#method.follower) only follow. Only interactions of
+/// the user are mirrored, programmatic changes are not.
+///
+/// Installing the link wraps the `on_scroll` and `on_select_item` callbacks
+/// of all leading tables, callbacks set before installing the link are still
+/// called.
+///
+/// # Examples
+///
+/// ```ignore
+/// TableLink::<Foo, BasicColumn>::new()
+///     .table("left")
+///     .table("right")
+///     .scroll(true)
+///     .follow_rows()
+///     .install(&mut siv);
+/// ```
+pub struct TableLink<T, H> {
+    tables: Vec<(String, bool)>,
+    scroll: bool,
+    follow: Option<FollowFocus>,
+    phantom: PhantomData<fn() -> (T, H)>,
+}
+
+impl<T, H> Default for TableLink<T, H>
+where
+    T: TableViewItem<H> + 'static,
+    H: Eq + Hash + Clone + MaybeSync + 'static,
+{
+    /// Creates a new link without any tables.
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, H> TableLink<T, H>
+where
+    T: TableViewItem<H> + 'static,
+    H: Eq + Hash + Clone + MaybeSync + 'static,
+{
+    /// Creates a new link without any tables.
+    ///
+    /// Scrolling is synchronized by default, focus changes are not.
+    pub fn new() -> Self {
+        Self {
+            tables: Vec::new(),
+            scroll: true,
+            follow: None,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Adds the table registered under `name`, which both leads and follows
+    /// the other tables.
+    pub fn add_table<S: Into<String>>(&mut self, name: S) {
+        self.tables.push((name.into(), true));
+    }
+
+    /// Adds the table registered under `name`, which both leads and follows
+    /// the other tables.
+    ///
+    /// Chainable variant.
+    pub fn table<S: Into<String>>(self, name: S) -> Self {
+        self.with(|l| l.add_table(name))
+    }
+
+    /// Adds the table registered under `name`, which only follows the other
+    /// tables.
+    pub fn add_follower<S: Into<String>>(&mut self, name: S) {
+        self.tables.push((name.into(), false));
+    }
+
+    /// Adds the table registered under `name`, which only follows the other
+    /// tables.
+    ///
+    /// Chainable variant.
+    pub fn follower<S: Into<String>>(self, name: S) -> Self {
+        self.with(|l| l.add_follower(name))
+    }
+
+    /// Sets whether the scroll offset is synchronized.
+    pub fn set_scroll(&mut self, scroll: bool) {
+        self.scroll = scroll;
+    }
+
+    /// Sets whether the scroll offset is synchronized.
+    ///
+    /// Chainable variant.
+    pub fn scroll(self, scroll: bool) -> Self {
+        self.with(|l| l.set_scroll(scroll))
+    }
+
+    /// Makes the other tables focus the same row as the table whose focus
+    /// changed.
+    pub fn set_follow_rows(&mut self) {
+        self.follow = Some(follow_rows::<T, H>);
+    }
+
+    /// Makes the other tables focus the same row as the table whose focus
+    /// changed.
+    ///
+    /// Chainable variant.
+    pub fn follow_rows(self) -> Self {
+        self.with(|l| l.set_follow_rows())
+    }
+
+    /// Installs the link on all tables which are currently registered.
+    ///
+    /// Tables which cannot be found are skipped.
+    pub fn install(&self, siv: &mut Cursive) {
+        for (name, _) in self.tables.iter().filter(|(_, leads)| *leads) {
+            let targets: Rc<[String]> = self
+                .tables
+                .iter()
+                .filter(|(other, _)| other != name)
+                .map(|(other, _)| other.clone())
+                .collect();
+            let scroll = self.scroll;
+            let follow = self.follow;
+            let source = name.clone();
+
+            siv.call_on_name(name, move |table: &mut TableView<T, H>| {
+                if scroll {
+                    let targets = Rc::clone(&targets);
+                    let previous = table.on_scroll.take();
+                    table.set_on_scroll(move |siv, offset| {
+                        if let Some(ref previous) = previous {
+                            previous(siv, offset);
+                        }
+                        for name in targets.iter() {
+                            siv.call_on_name(name, |table: &mut TableView<T, H>| {
+                                table.set_scroll_offset(offset);
+                            });
+                        }
+                    });
+                }
+                if let Some(follow) = follow {
+                    let previous = table.on_select.take();
+                    table.set_on_select_item(move |siv, row, item| {
+                        if let Some(ref previous) = previous {
+                            previous(siv, row, item);
+                        }
+                        follow(siv, &source, row, item, &targets);
+                    });
+                }
+            });
+        }
+    }
+}
+
+impl<T, H> TableLink<T, H>
+where
+    T: KeyedTableViewItem<H> + 'static,
+    H: Eq + Hash + Clone + MaybeSync + 'static,
+{
+    /// Makes the other tables focus the item with the same key as the item
+    /// focused in the table whose focus changed.
+    ///
+    /// Tables without such an item keep their focus.
+    pub fn set_follow_keys(&mut self) {
+        self.follow = Some(follow_keys::<T, H>);
+    }
+
+    /// Makes the other tables focus the item with the same key as the item
+    /// focused in the table whose focus changed.
+    ///
+    /// Chainable variant.
+    pub fn follow_keys(self) -> Self {
+        self.with(|l| l.set_follow_keys())
+    }
+}
+
+fn follow_rows<T, H>(siv: &mut Cursive, _: &str, row: RowIndex, _: ItemIndex, targets: &[String])
+where
+    T: TableViewItem<H> + 'static,
+    H: Eq + Hash + Clone + MaybeSync + 'static,
+{
+    for name in targets {
+        siv.call_on_name(name, |table: &mut TableView<T, H>| table.focus_row(row));
+    }
+}
+
+fn follow_keys<T, H>(
+    siv: &mut Cursive,
+    source: &str,
+    _: RowIndex,
+    item: ItemIndex,
+    targets: &[String],
+) where
+    T: KeyedTableViewItem<H> + 'static,
+    H: Eq + Hash + Clone + MaybeSync + 'static,
+{
+    let key = siv
+        .call_on_name(source, |table: &mut TableView<T, H>| {
+            table.borrow_item(item).map(|item| item.key())
+        })
+        .flatten();
+
+    if let Some(key) = key {
+        for name in targets {
+            siv.call_on_name(name, |table: &mut TableView<T, H>| {
+                if let Some(index) = table.find_by_key(&key) {
+                    table.focus_item(index);
+                }
+            });
+        }
+    }
+}
@@ -0,0 +1,253 @@
+// STD Dependencies -----------------------------------------------------------
+use std::cmp;
+use std::hash::Hash;
+
+// External Dependencies ------------------------------------------------------
+use cursive::{
+    direction::{Direction, Orientation},
+    event::{AnyCb, Event, EventResult},
+    utils::markup::StyledString,
+    vec::Vec2,
+    view::{Selector, View, ViewNotFound},
+    views::{DummyView, TextView},
+    Printer, Rect, With,
+};
+
+// Modules --------------------------------------------------------------------
+use super::{ItemIndex, MaybeSync, TableView, TableViewItem};
+
+/// Callback creating the detail view for an item.
+///
+/// This is a private type to help readability.
+type DetailCallback<T> = Box<dyn Fn(&T) -> Box<dyn View>>;
+
+/// A [`TableView`](struct.TableView.html) combined with a detail pane, which
+/// shows the currently focused item.
+///
+/// The detail pane is rebuilt whenever the focused item changes. Keyboard
+/// input goes to the table, mouse input to the pane below the cursor.
+///
+/// # Examples
+///
+/// ```ignore
+/// let view = MasterDetailView::with_text(table, |item: &Foo| {
+///     format!("Name: {}\nCount: {}", item.name, item.count)
+/// })
+/// .orientation(Orientation::Horizontal)
+/// .ratio(0.6);
+/// ```
+pub struct MasterDetailView<T, H> {
+    table: TableView<T, H>,
+    detail: Box<dyn View>,
+    create_detail: DetailCallback<T>,
+    detail_item: Option<usize>,
+
+    orientation: Orientation,
+    ratio: f32,
+    split: usize,
+    size: Vec2,
+}
+
+impl<T, H> MasterDetailView<T, H>
+where
+    T: TableViewItem<H> + 'static,
+    H: Eq + Hash + Clone + MaybeSync + 'static,
+{
+    /// Creates a new `MasterDetailView` around `table`, using `detail` to
+    /// create the detail view for the focused item.
+    ///
+    /// The detail pane is placed below the table and both share the
+    /// available space equally.
+    pub fn new<F>(table: TableView<T, H>, detail: F) -> Self
+    where
+        F: Fn(&T) -> Box<dyn View> + 'static,
+    {
+        let mut view = Self {
+            table,
+            detail: Box::new(DummyView),
+            create_detail: Box::new(detail),
+            detail_item: None,
+
+            orientation: Orientation::Vertical,
+            ratio: 0.5,
+            split: 0,
+            size: Vec2::zero(),
+        };
+        view.refresh();
+        view
+    }
+
+    /// Creates a new `MasterDetailView` around `table`, which shows the text
+    /// returned by `text` for the focused item in a `TextView`.
+    pub fn with_text<F, S>(table: TableView<T, H>, text: F) -> Self
+    where
+        F: Fn(&T) -> S + 'static,
+        S: Into<StyledString>,
+    {
+        Self::new(table, move |item| Box::new(TextView::new(text(item))))
+    }
+
+    /// Sets how the table and the detail pane are arranged.
+    ///
+    /// With `Orientation::Vertical`, the default, the detail pane is placed
+    /// below the table, otherwise beside it.
+    pub fn set_orientation(&mut self, orientation: Orientation) {
+        self.orientation = orientation;
+    }
+
+    /// Sets how the table and the detail pane are arranged.
+    ///
+    /// Chainable variant.
+    pub fn orientation(self, orientation: Orientation) -> Self {
+        self.with(|v| v.set_orientation(orientation))
+    }
+
+    /// Sets the share of the available space taken by the table, between
+    /// `0.0` and `1.0`.
+    ///
+    /// Defaults to `0.5`.
+    pub fn set_ratio(&mut self, ratio: f32) {
+        self.ratio = ratio.clamp(0.0, 1.0);
+    }
+
+    /// Sets the share of the available space taken by the table.
+    ///
+    /// Chainable variant.
+    pub fn ratio(self, ratio: f32) -> Self {
+        self.with(|v| v.set_ratio(ratio))
+    }
+
+    /// Returns a reference to the wrapped table.
+    pub fn get_table(&self) -> &TableView<T, H> {
+        &self.table
+    }
+
+    /// Returns a mutable reference to the wrapped table.
+    ///
+    /// The detail pane is updated during the next layout.
+    pub fn get_table_mut(&mut self) -> &mut TableView<T, H> {
+        &mut self.table
+    }
+
+    /// Returns a mutable reference to the current detail view.
+    pub fn get_detail_mut(&mut self) -> &mut dyn View {
+        &mut *self.detail
+    }
+
+    /// Rebuilds the detail pane for the focused item, e.g. after the item
+    /// was modified in place.
+    pub fn refresh(&mut self) {
+        self.detail_item = self.table.focused_item().map(usize::from);
+        self.detail = match self
+            .detail_item
+            .and_then(|i| self.table.borrow_item(ItemIndex(i)))
+        {
+            Some(item) => (self.create_detail)(item),
+            None => Box::new(DummyView),
+        };
+    }
+
+    fn update_detail(&mut self) {
+        if self.table.focused_item().map(usize::from) != self.detail_item {
+            self.refresh();
+        }
+    }
+
+    fn detail_offset(&self) -> Vec2 {
+        self.orientation.make_vec(self.split + 1, 0)
+    }
+
+    fn detail_size(&self) -> Vec2 {
+        let main = self.orientation.get(&self.size);
+        let second = self.orientation.swap().get(&self.size);
+        self.orientation
+            .make_vec(main.saturating_sub(self.split + 1), second)
+    }
+}
+
+impl<T, H> View for MasterDetailView<T, H>
+where
+    T: TableViewItem<H> + 'static,
+    H: Eq + Hash + Clone + MaybeSync + 'static,
+{
+    fn draw(&self, printer: &Printer) {
+        let second = self.orientation.swap().get(&printer.size);
+        self.table
+            .draw(&printer.cropped(self.orientation.make_vec(self.split, second)));
+
+        match self.orientation {
+            Orientation::Vertical => printer.print_hline((0, self.split), second, "─"),
+            Orientation::Horizontal => printer.print_vline((self.split, 0), second, "│"),
+        }
+
+        self.detail.draw(
+            &printer
+                .offset(self.detail_offset())
+                .cropped(self.detail_size())
+                .focused(false),
+        );
+    }
+
+    fn layout(&mut self, size: Vec2) {
+        self.update_detail();
+
+        let main = self.orientation.get(&size);
+        self.size = size;
+        self.split = cmp::min(
+            (main.saturating_sub(1) as f32 * self.ratio).round() as usize,
+            main.saturating_sub(1),
+        );
+
+        let second = self.orientation.swap().get(&size);
+        self.table
+            .layout(self.orientation.make_vec(self.split, second));
+        self.detail.layout(self.detail_size());
+    }
+
+    fn needs_relayout(&self) -> bool {
+        self.table.needs_relayout() || self.detail.needs_relayout()
+    }
+
+    fn required_size(&mut self, constraint: Vec2) -> Vec2 {
+        let table = self.table.required_size(constraint);
+        let detail = self.detail.required_size(constraint);
+        self.orientation
+            .stack(vec![table, self.orientation.make_vec(1, 0), detail].into_iter())
+    }
+
+    fn on_event(&mut self, event: Event) -> EventResult {
+        let result = match event {
+            Event::Mouse {
+                position, offset, ..
+            } if position
+                .checked_sub(offset + self.detail_offset())
+                .map_or(false, |p| p.strictly_lt(self.detail_size())) =>
+            {
+                self.detail
+                    .on_event(event.relativized(self.detail_offset()))
+            }
+            event => View::on_event(&mut self.table, event),
+        };
+        self.update_detail();
+        result
+    }
+
+    fn call_on_any<'a>(&mut self, selector: &Selector<'_>, callback: AnyCb<'a>) {
+        self.table.call_on_any(selector, &mut *callback);
+        self.detail.call_on_any(selector, callback);
+    }
+
+    fn focus_view(&mut self, selector: &Selector<'_>) -> Result<(), ViewNotFound> {
+        self.table.focus_view(selector)
+    }
+
+    fn take_focus(&mut self, source: Direction) -> bool {
+        self.table.take_focus(source)
+    }
+
+    fn important_area(&self, view_size: Vec2) -> Rect {
+        let second = self.orientation.swap().get(&view_size);
+        self.table
+            .important_area(self.orientation.make_vec(self.split, second))
+    }
+}